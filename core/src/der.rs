@@ -3,7 +3,10 @@ use core::{
     mem,
 };
 
-use crate::{error::Error, scalar::Scalar};
+use crate::{
+    error::{DerError, DerField, Error},
+    scalar::Scalar,
+};
 
 pub struct SignatureArray([u8; 6 + 33 + 33], usize);
 
@@ -33,11 +36,30 @@ impl AsMut<[u8]> for SignatureArray {
     }
 }
 
-pub struct Decoder<'a>(&'a [u8], usize);
+/// DER decoder. Failures are reported as `Error::InvalidDer`, tagged with
+/// the field being decoded and the offset at which decoding stopped.
+pub struct Decoder<'a>(&'a [u8], usize, DerField);
 
 impl<'a> Decoder<'a> {
     pub fn new(arr: &'a [u8]) -> Self {
-        Decoder(arr, 0)
+        Decoder(arr, 0, DerField::SequenceTag)
+    }
+
+    /// Current offset in the input.
+    pub fn offset(&self) -> usize {
+        self.1
+    }
+
+    /// Build an error for `field` at the current offset.
+    pub fn error(&self, field: DerField) -> Error {
+        Error::InvalidDer(DerError {
+            field,
+            offset: self.1,
+        })
+    }
+
+    fn fail(&self) -> Error {
+        self.error(self.2)
     }
 
     pub fn remaining_len(&self) -> usize {
@@ -46,7 +68,7 @@ impl<'a> Decoder<'a> {
 
     pub fn read(&mut self) -> Result<u8, Error> {
         if self.1 >= self.0.len() {
-            Err(Error::InvalidSignature)
+            Err(self.fail())
         } else {
             let v = self.0[self.1];
             self.1 += 1;
@@ -56,7 +78,7 @@ impl<'a> Decoder<'a> {
 
     pub fn peek(&self, forward: usize) -> Result<u8, Error> {
         if self.1 + forward >= self.0.len() {
-            Err(Error::InvalidSignature)
+            Err(self.fail())
         } else {
             let v = self.0[self.1 + forward];
             Ok(v)
@@ -65,7 +87,7 @@ impl<'a> Decoder<'a> {
 
     pub fn peek_slice(&self, len: usize) -> Result<&[u8], Error> {
         if (len == 0 && self.1 >= self.0.len()) || self.1 + len > self.0.len() {
-            Err(Error::InvalidSignature)
+            Err(self.fail())
         } else {
             let v = &self.0[self.1..(self.1 + len)];
             Ok(v)
@@ -74,7 +96,7 @@ impl<'a> Decoder<'a> {
 
     pub fn skip(&mut self, len: usize) -> Result<(), Error> {
         if (len == 0 && self.1 >= self.0.len()) || self.1 + len > self.0.len() {
-            Err(Error::InvalidSignature)
+            Err(self.fail())
         } else {
             self.1 += len;
             Ok(())
@@ -82,18 +104,21 @@ impl<'a> Decoder<'a> {
     }
 
     pub fn read_constructed_sequence(&mut self) -> Result<(), Error> {
+        self.2 = DerField::SequenceTag;
         let v = self.read()?;
         if v == 0x30 {
+            self.2 = DerField::SequenceLength;
             Ok(())
         } else {
-            Err(Error::InvalidSignature)
+            self.1 -= 1;
+            Err(self.fail())
         }
     }
 
     pub fn read_len(&mut self) -> Result<usize, Error> {
         let b1 = self.read()?;
         if b1 == 0xff {
-            return Err(Error::InvalidSignature);
+            return Err(self.fail());
         }
 
         // Short form
@@ -103,56 +128,60 @@ impl<'a> Decoder<'a> {
 
         // Infinite length is not allowed
         if b1 == 0x80 {
-            return Err(Error::InvalidSignature);
+            return Err(self.fail());
         }
 
         let mut lenleft = (b1 & 0x7f) as usize;
         if lenleft > self.remaining_len() {
-            return Err(Error::InvalidSignature);
+            return Err(self.fail());
         }
 
         if self.peek(0)? == 0 {
             // Not the shortest possible length encoding
-            return Err(Error::InvalidSignature);
+            return Err(self.fail());
         }
 
         if lenleft > mem::size_of::<usize>() {
-            return Err(Error::InvalidSignature);
+            return Err(self.fail());
         }
 
         let mut ret = 0;
         while lenleft > 0 {
             ret = (ret << 8) | (self.read()? as usize);
             if ret + lenleft > self.remaining_len() {
-                return Err(Error::InvalidSignature);
+                return Err(self.fail());
             }
             lenleft -= 1;
         }
 
         if ret < 128 {
             // Not the shortest possible length encoding
-            return Err(Error::InvalidSignature);
+            return Err(self.fail());
         }
 
         Ok(ret)
     }
 
     pub fn read_integer(&mut self) -> Result<Scalar, Error> {
+        self.2 = DerField::IntegerTag;
         if self.read()? != 0x02 {
-            return Err(Error::InvalidSignature);
+            self.1 -= 1;
+            return Err(self.fail());
         }
 
+        self.2 = DerField::IntegerLength;
         let mut rlen = self.read_len()?;
         if rlen == 0 || rlen > self.remaining_len() {
-            return Err(Error::InvalidSignature);
+            return Err(self.fail());
         }
 
+        self.2 = DerField::IntegerValue;
         if self.peek(0)? == 0x00 && rlen > 1 && (self.peek(1)? & 0x80) == 0x00 {
-            return Err(Error::InvalidSignature);
+            return Err(self.fail());
         }
 
         if self.peek(0)? == 0xff && rlen > 1 && (self.peek(1)? & 0x80) == 0x00 {
-            return Err(Error::InvalidSignature);
+            return Err(self.fail());
         }
 
         let mut overflow = false;
@@ -174,11 +203,13 @@ impl<'a> Decoder<'a> {
 
         if !overflow {
             let mut b32 = [0u8; 32];
-            b32[32 - rlen..].copy_from_slice(self.peek_slice(rlen)?);
-            self.skip(rlen)?;
+            b32[32 - rlen..].copy_from_slice(&self.0[self.1..self.1 + rlen]);
 
             overflow |= bool::from(int.set_b32(&b32));
         }
+        // The length was checked against the remaining input above, and the
+        // value is skipped even when it overflows.
+        self.1 += rlen;
 
         if overflow {
             int = Scalar::default();
@@ -192,7 +223,7 @@ impl<'a> Decoder<'a> {
         if len & 0x80 != 0x00 {
             len -= 0x80;
             if len as usize > self.remaining_len() {
-                return Err(Error::InvalidSignature);
+                return Err(self.fail());
             }
             self.skip(len as usize)?;
         }
@@ -206,14 +237,14 @@ impl<'a> Decoder<'a> {
         if len & 0x80 != 0x00 {
             len -= 0x80;
             if len as usize > self.remaining_len() {
-                return Err(Error::InvalidSignature);
+                return Err(self.fail());
             }
             while len > 0 && self.peek(0)? == 0 {
                 self.skip(1)?;
                 len -= 1;
             }
             if (len as usize) >= mem::size_of::<usize>() {
-                return Err(Error::InvalidSignature);
+                return Err(self.fail());
             }
             while len > 0 {
                 ret = (ret << 8) + (self.read()? as usize);
@@ -223,20 +254,26 @@ impl<'a> Decoder<'a> {
             ret = len as usize;
         }
         if ret > self.remaining_len() {
-            return Err(Error::InvalidSignature);
+            return Err(self.fail());
         }
 
         Ok(ret)
     }
 
-    pub fn read_integer_lax(&mut self) -> Result<Scalar, Error> {
+    /// Read an integer, leniently. Returns `None` if the value does not fit
+    /// in a scalar.
+    pub fn read_integer_lax(&mut self) -> Result<Option<Scalar>, Error> {
         // Integer tag byte.
+        self.2 = DerField::IntegerTag;
         if self.read()? != 0x02 {
-            return Err(Error::InvalidSignature);
+            self.1 -= 1;
+            return Err(self.fail());
         }
 
+        self.2 = DerField::IntegerLength;
         let mut len = self.read_len_lax()?;
 
+        self.2 = DerField::IntegerValue;
         // Ignore leading zeroes.
         while len > 0 && self.peek(0)? == 0 {
             len -= 1;
@@ -253,16 +290,18 @@ impl<'a> Decoder<'a> {
 
         if !overflow {
             let mut b32 = [0u8; 32];
-            b32[32 - len..].copy_from_slice(&self.peek_slice(len)?);
-            self.skip(len)?;
+            b32[32 - len..].copy_from_slice(&self.0[self.1..self.1 + len]);
 
             overflow |= bool::from(int.set_b32(&b32));
         }
+        // The length was checked against the remaining input above, and the
+        // value is skipped even when it overflows.
+        self.1 += len;
 
         if overflow {
-            int = Scalar::default();
+            Ok(None)
+        } else {
+            Ok(Some(int))
         }
-
-        Ok(int)
     }
 }
@@ -6,15 +6,16 @@ use crate::{
 use digest::{generic_array::GenericArray, Digest, FixedOutputReset};
 
 impl ECMultContext {
-    pub fn ecdh_raw<D: Digest + Default + FixedOutputReset>(
-        &self,
-        point: &Affine,
-        scalar: &Scalar,
-    ) -> Option<GenericArray<u8, D::OutputSize>> {
-        let mut digest: D = Default::default();
-
+    /// Multiply `point` by `scalar`, and pass the affine coordinates of the
+    /// result, as 32 bytes big endian each, to `hasher`. The multiplication
+    /// runs in constant time with respect to `scalar`; `hasher` is expected
+    /// to do the same. Returns `None` if `scalar` is zero.
+    pub fn ecdh_with_hasher<F, R>(&self, point: &Affine, scalar: &Scalar, hasher: F) -> Option<R>
+    where
+        F: FnOnce(&[u8; 32], &[u8; 32]) -> R,
+    {
         let mut pt = *point;
-        let s = *scalar;
+        let mut s = *scalar;
 
         if s.is_zero() {
             return None;
@@ -27,11 +28,32 @@ impl ECMultContext {
         pt.x.normalize();
         pt.y.normalize();
 
-        let x = pt.x.b32();
-        let y = 0x02 | (if pt.y.is_odd() { 1 } else { 0 });
+        let mut x = pt.x.b32();
+        let mut y = pt.y.b32();
+
+        let ret = hasher(&x, &y);
+
+        unsafe {
+            core::ptr::write_volatile(&mut x, [0u8; 32]);
+            core::ptr::write_volatile(&mut y, [0u8; 32]);
+        }
+        s.clear();
+        res.clear();
+        pt.clear();
+
+        Some(ret)
+    }
 
-        Digest::update(&mut digest, &[y]);
-        Digest::update(&mut digest, &x);
-        Some(digest.finalize_reset())
+    pub fn ecdh_raw<D: Digest + Default + FixedOutputReset>(
+        &self,
+        point: &Affine,
+        scalar: &Scalar,
+    ) -> Option<GenericArray<u8, D::OutputSize>> {
+        self.ecdh_with_hasher(point, scalar, |x, y| {
+            let mut digest: D = Default::default();
+            Digest::update(&mut digest, [0x02 | (y[31] & 1)]);
+            Digest::update(&mut digest, x);
+            digest.finalize_reset()
+        })
     }
 }
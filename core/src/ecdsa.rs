@@ -5,6 +5,7 @@ use crate::{
     scalar::Scalar,
     Error,
 };
+use subtle::Choice;
 
 const P_MINUS_ORDER: Field = Field::new(0, 0, 0, 1, 0x45512319, 0x50B75FC4, 0x402DA172, 0x2FC9BAEE);
 
@@ -122,7 +123,7 @@ impl ECMultGenContext {
         debug_assert!(!sigr.is_zero());
         debug_assert!(!overflow);
 
-        let mut recid = (if overflow { 2 } else { 0 }) | (if r.y.is_odd() { 1 } else { 0 });
+        let mut recid = ((overflow as u8) << 1) | (r.y.is_odd() as u8);
         let mut n = &sigr * seckey;
         n += message;
         let mut sigs = nonce.inv();
@@ -133,10 +134,9 @@ impl ECMultGenContext {
         if sigs.is_zero() {
             return Err(Error::InvalidMessage);
         }
-        if sigs.is_high() {
-            sigs = -sigs;
-            recid ^= 1;
-        }
+        let high = sigs.is_high();
+        sigs.cond_neg_assign(Choice::from(high as u8));
+        recid ^= high as u8;
         Ok((sigr, sigs, recid))
     }
 }
@@ -1,17 +1,39 @@
+#[cfg(feature = "std")]
+use crate::group::{set_table_gej_var, AFFINE_G};
 use crate::{
     field::Field,
-    group::{globalz_set_table_gej, set_table_gej_var, Affine, AffineStorage, Jacobian, AFFINE_G},
+    group::{globalz_set_table_gej, Affine, AffineStorage, Jacobian, AFFINE_INFINITY},
     scalar::Scalar,
 };
+#[cfg(feature = "std")]
 use std::alloc::{alloc, Layout};
 use subtle::Choice;
 
 pub const WINDOW_A: usize = 5;
+/// Window size of the `ECMultContext` generator table. The table holds
+/// `2^(WINDOW_G - 2)` points, see the `small-tables` and `medium-tables`
+/// features.
+#[cfg(feature = "small-tables")]
+pub const WINDOW_G: usize = 6;
+#[cfg(all(feature = "medium-tables", not(feature = "small-tables")))]
+pub const WINDOW_G: usize = 10;
+#[cfg(not(any(feature = "small-tables", feature = "medium-tables")))]
 pub const WINDOW_G: usize = 16;
 pub const ECMULT_TABLE_SIZE_A: usize = 1 << (WINDOW_A - 2);
 pub const ECMULT_TABLE_SIZE_G: usize = 1 << (WINDOW_G - 2);
+
+/// Number of scalar bits consumed per `ECMultGenContext` table row.
+#[cfg(feature = "small-tables")]
+pub const ECMULT_GEN_PREC_BITS: usize = 2;
+#[cfg(not(feature = "small-tables"))]
+pub const ECMULT_GEN_PREC_BITS: usize = 4;
+/// Number of points in each `ECMultGenContext` table row.
+pub const ECMULT_GEN_PREC_G: usize = 1 << ECMULT_GEN_PREC_BITS;
+/// Number of rows in the `ECMultGenContext` table.
+pub const ECMULT_GEN_PREC_N: usize = 256 / ECMULT_GEN_PREC_BITS;
 pub const WNAF_BITS: usize = 256;
 
+#[cfg(feature = "std")]
 fn odd_multiples_table_storage_var(pre: &mut [AffineStorage], a: &Jacobian) {
     let mut prej: Vec<Jacobian> = Vec::with_capacity(pre.len());
     for _ in 0..pre.len() {
@@ -56,6 +78,7 @@ impl ECMultContext {
     }
 
     /// Generate a new `ECMultContext` on the heap. Note that this function is expensive.
+    #[cfg(feature = "std")]
     pub fn new_boxed() -> Box<Self> {
         // This unsafe block allocates a new, unitialized `ECMultContext` and
         // then fills in the value. This is to avoid allocating it on stack
@@ -81,57 +104,72 @@ impl ECMultContext {
 }
 
 /// Set a batch of group elements equal to the inputs given in jacobian
-/// coordinates. Not constant time.
-pub fn set_all_gej_var(a: &[Jacobian]) -> Vec<Affine> {
-    let mut az: Vec<Field> = Vec::with_capacity(a.len());
-    for point in a {
-        if !point.is_infinity() {
-            az.push(point.z);
+/// coordinates, writing the results to `r`. Not constant time.
+pub fn set_all_gej_var(r: &mut [Affine], a: &[Jacobian]) {
+    debug_assert!(r.len() == a.len());
+
+    let mut last_i: Option<usize> = None;
+    for i in 0..a.len() {
+        if a[i].is_infinity() {
+            r[i] = AFFINE_INFINITY;
+        } else {
+            // Use destination's x coordinates as scratch space.
+            r[i].x = match last_i {
+                Some(last_i) => r[last_i].x * a[i].z,
+                None => a[i].z,
+            };
+            last_i = Some(i);
         }
     }
-    let azi: Vec<Field> = inv_all_var(&az);
 
-    let mut ret = vec![Affine::default(); a.len()];
+    let mut last_i = match last_i {
+        Some(last_i) => last_i,
+        None => return,
+    };
+    let mut u = r[last_i].x.inv_var();
+
+    for i in (0..last_i).rev() {
+        if !a[i].is_infinity() {
+            r[last_i].x = r[i].x * u;
+            u *= a[last_i].z;
+            last_i = i;
+        }
+    }
+    debug_assert!(!a[last_i].is_infinity());
+    r[last_i].x = u;
 
-    let mut count = 0;
     for i in 0..a.len() {
-        ret[i].infinity = a[i].infinity;
         if !a[i].is_infinity() {
-            ret[i].set_gej_zinv(&a[i], &azi[count]);
-            count += 1;
+            let zi = r[i].x;
+            r[i].set_gej_zinv(&a[i], &zi);
         }
     }
-    ret
 }
 
-/// Calculate the (modular) inverses of a batch of field
-/// elements. Requires the inputs' magnitudes to be at most 8. The
-/// output magnitudes are 1 (but not guaranteed to be
+/// Calculate the (modular) inverses of a batch of field elements,
+/// writing them to `r`. Requires the inputs' magnitudes to be at most
+/// 8. The output magnitudes are 1 (but not guaranteed to be
 /// normalized).
-pub fn inv_all_var(fields: &[Field]) -> Vec<Field> {
-    if fields.is_empty() {
-        return Vec::new();
-    }
+pub fn inv_all_var(r: &mut [Field], a: &[Field]) {
+    debug_assert!(r.len() == a.len());
 
-    let mut ret = Vec::with_capacity(fields.len());
-    ret.push(fields[0]);
+    if a.is_empty() {
+        return;
+    }
 
-    for i in 1..fields.len() {
-        ret.push(Field::default());
-        ret[i] = ret[i - 1] * fields[i];
+    r[0] = a[0];
+    for i in 1..a.len() {
+        r[i] = r[i - 1] * a[i];
     }
 
-    let mut u = ret[fields.len() - 1].inv_var();
+    let mut u = r[a.len() - 1].inv_var();
 
-    for i in (1..fields.len()).rev() {
-        let j = i;
-        let i = i - 1;
-        ret[j] = ret[i] * u;
-        u *= fields[j];
+    for i in (1..a.len()).rev() {
+        r[i] = r[i - 1] * u;
+        u *= a[i];
     }
 
-    ret[0] = u;
-    ret
+    r[0] = u;
 }
 
 const GEN_BLIND: Scalar = Scalar([
@@ -155,7 +193,7 @@ const GEN_INITIAL: Jacobian = Jacobian {
 
 /// Context for accelerating the computation of a*G.
 pub struct ECMultGenContext {
-    prec: [[AffineStorage; 16]; 64],
+    prec: [[AffineStorage; ECMULT_GEN_PREC_G]; ECMULT_GEN_PREC_N],
     blind: Scalar,
     initial: Jacobian,
 }
@@ -167,7 +205,9 @@ impl ECMultGenContext {
     /// The function is unsafe because incorrect value of `pre_g` can lead to
     /// crypto logic failure. You most likely do not want to use this function,
     /// but `ECMultGenContext::new_boxed`.
-    pub const unsafe fn new_from_raw(prec: [[AffineStorage; 16]; 64]) -> Self {
+    pub const unsafe fn new_from_raw(
+        prec: [[AffineStorage; ECMULT_GEN_PREC_G]; ECMULT_GEN_PREC_N],
+    ) -> Self {
         Self {
             prec,
             blind: GEN_BLIND,
@@ -176,11 +216,12 @@ impl ECMultGenContext {
     }
 
     /// Inspect `ECMultGenContext` values.
-    pub fn inspect_raw(&self) -> &[[AffineStorage; 16]; 64] {
+    pub fn inspect_raw(&self) -> &[[AffineStorage; ECMULT_GEN_PREC_G]; ECMULT_GEN_PREC_N] {
         &self.prec
     }
 
     /// Generate a new `ECMultGenContext` on the heap. Note that this function is expensive.
+    #[cfg(feature = "std")]
     pub fn new_boxed() -> Box<Self> {
         // This unsafe block allocates a new, unitialized `ECMultGenContext` and
         // then fills in the value. This is to avoid allocating it on stack
@@ -190,8 +231,8 @@ impl ECMultGenContext {
             let ptr = alloc(Layout::new::<ECMultGenContext>()) as *mut ECMultGenContext;
             let mut this = Box::from_raw(ptr);
 
-            for j in 0..64 {
-                for i in 0..16 {
+            for j in 0..ECMULT_GEN_PREC_N {
+                for i in 0..ECMULT_GEN_PREC_G {
                     this.prec[j][i] = AffineStorage::default();
                 }
             }
@@ -220,31 +261,35 @@ impl ECMultGenContext {
         nums_gej = nums_gej.add_ge_var(&AFFINE_G, None);
 
         // Compute prec.
-        let mut precj: Vec<Jacobian> = Vec::with_capacity(1024);
-        for _ in 0..1024 {
+        const PREC_LEN: usize = ECMULT_GEN_PREC_N * ECMULT_GEN_PREC_G;
+        let mut precj: Vec<Jacobian> = Vec::with_capacity(PREC_LEN);
+        for _ in 0..PREC_LEN {
             precj.push(Jacobian::default());
         }
         let mut gbase = gj;
         let mut numsbase = nums_gej;
-        for j in 0..64 {
-            precj[j * 16] = numsbase;
-            for i in 1..16 {
-                precj[j * 16 + i] = precj[j * 16 + i - 1].add_var(&gbase, None);
+        for j in 0..ECMULT_GEN_PREC_N {
+            let row = j * ECMULT_GEN_PREC_G;
+            precj[row] = numsbase;
+            for i in 1..ECMULT_GEN_PREC_G {
+                precj[row + i] = precj[row + i - 1].add_var(&gbase, None);
             }
-            for _ in 0..4 {
+            for _ in 0..ECMULT_GEN_PREC_BITS {
                 gbase = gbase.double_var(None);
             }
             numsbase = numsbase.double_var(None);
-            if j == 62 {
+            if j == ECMULT_GEN_PREC_N - 2 {
+                // In the last row, numsbase is (1 - 2^j) * nums instead.
                 numsbase = numsbase.neg();
                 numsbase = numsbase.add_var(&nums_gej, None);
             }
         }
-        let prec = set_all_gej_var(&precj);
+        let mut prec = vec![Affine::default(); PREC_LEN];
+        set_all_gej_var(&mut prec, &precj);
 
-        for j in 0..64 {
-            for i in 0..16 {
-                let pg: AffineStorage = prec[j * 16 + i].into();
+        for j in 0..ECMULT_GEN_PREC_N {
+            for i in 0..ECMULT_GEN_PREC_G {
+                let pg: AffineStorage = prec[j * ECMULT_GEN_PREC_G + i].into();
                 this.prec[j][i] = pg;
             }
         }
@@ -305,8 +350,9 @@ fn table_get_ge(r: &mut Affine, pre: &[Affine], n: i32, w: usize) {
 }
 
 fn table_get_ge_const(r: &mut Affine, pre: &[Affine], n: i32, w: usize) {
-    let abs_n = n * (if n > 0 { 1 } else { 0 } * 2 - 1);
-    let idx_n = abs_n / 2;
+    let mask = n >> 31;
+    let abs_n = (n + mask) ^ mask;
+    let idx_n = abs_n >> 1;
     debug_assert!(n & 1 == 1);
     debug_assert!(n >= -((1 << (w - 1)) - 1));
     debug_assert!(n <= ((1 << (w - 1)) - 1));
@@ -408,28 +454,29 @@ pub fn ecmult_wnaf_const(wnaf: &mut [i32], a: &Scalar, w: usize) -> i32 {
     let bit = flip ^ !s.is_even();
     /* We add 1 to even numbers, 2 to odd ones, noting that negation
      * flips parity */
-    let neg_s = -s;
+    let mut neg_s = -s;
     let not_neg_one = !neg_s.is_one();
-    s.cadd_bit(if bit { 1 } else { 0 }, not_neg_one);
+    neg_s.clear();
+    s.cadd_bit(bit as usize, not_neg_one);
     /* If we had negative one, flip == 1, s.d[0] == 0, bit == 1, so
      * caller expects that we added two to it and flipped it. In fact
      * for -1 these operations are identical. We only flipped, but
      * since skewing is required (in the sense that the skew must be 1
      * or 2, never zero) and flipping is not, we need to change our
      * flags to claim that we only skewed. */
-    let mut global_sign = if flip { -1 } else { 1 };
+    let mut global_sign = 1 - 2 * (flip as i32);
     s.cond_neg_assign(Choice::from(flip as u8));
-    global_sign *= if not_neg_one { 1 } else { 0 } * 2 - 1;
-    let skew = 1 << (if bit { 1 } else { 0 });
+    global_sign *= (not_neg_one as i32) * 2 - 1;
+    let skew = 1 << (bit as i32);
 
     let mut u_last: i32 = s.shr_int(w) as i32;
     let mut u: i32 = 0;
     while word * w < WNAF_BITS {
         u = s.shr_int(w) as i32;
         let even = (u & 1) == 0;
-        let sign = 2 * (if u_last > 0 { 1 } else { 0 }) - 1;
-        u += sign * if even { 1 } else { 0 };
-        u_last -= sign * if even { 1 } else { 0 } * (1 << w);
+        let sign = 2 * ((u_last > 0) as i32) - 1;
+        u += sign * (even as i32);
+        u_last -= sign * (even as i32) * (1 << w);
 
         wnaf[word] = (u_last as i32 * global_sign as i32) as i32;
         word += 1;
@@ -492,7 +539,7 @@ impl ECMultContext {
 
         let mut wnaf_1 = [0i32; 1 + WNAF_SIZE];
 
-        let sc = *scalar;
+        let mut sc = *scalar;
         let skew_1 = ecmult_wnaf_const(&mut wnaf_1, &sc, WINDOW_A - 1);
 
         /* Calculate odd multiples of a.  All multiples are brought to
@@ -547,7 +594,16 @@ impl ECMultContext {
         /* Apply the correction */
         correction = correction_1_stor.into();
         correction = correction.neg();
-        *r = r.add_ge(&correction)
+        *r = r.add_ge(&correction);
+
+        /* Clear everything derived from the scalar */
+        unsafe {
+            core::ptr::write_volatile(&mut wnaf_1, [0i32; 1 + WNAF_SIZE]);
+        }
+        sc.clear();
+        tmpa.clear();
+        correction.clear();
+        correction_1_stor.clear();
     }
 }
 
@@ -560,9 +616,9 @@ impl ECMultGenContext {
         let mut add = Affine::default();
         add.infinity = false;
 
-        for j in 0..64 {
-            let mut bits = gnb.bits(j * 4, 4);
-            for i in 0..16 {
+        for j in 0..ECMULT_GEN_PREC_N {
+            let mut bits = gnb.bits(j * ECMULT_GEN_PREC_BITS, ECMULT_GEN_PREC_BITS);
+            for i in 0..ECMULT_GEN_PREC_G {
                 adds.cmov(&self.prec[j][i], i as u32 == bits);
             }
             add = adds.into();
@@ -572,6 +628,7 @@ impl ECMultGenContext {
                 bits = 0;
             }
         }
+        adds.clear();
         add.clear();
         gnb.clear();
     }
@@ -1,3 +1,10 @@
+/// Errors of this crate.
+///
+/// The parsers report malformed input in detail, through `InvalidDer`,
+/// `InvalidPublicKeyEncoding` and `InvalidScalar`. The other entry points
+/// return the coarse variants: `InvalidSignature` from recovery,
+/// `InvalidPublicKey` from public key combination and `InvalidSecretKey`
+/// from ECDH.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Error {
     InvalidSignature,
@@ -8,13 +15,70 @@ pub enum Error {
     InvalidInputLength,
     TweakOutOfRange,
     InvalidAffine,
+    /// A ciphertext failed authentication.
+    InvalidCiphertext,
+    /// A DER-encoded signature could not be decoded.
+    InvalidDer(DerError),
+    /// A serialized public key could not be parsed.
+    InvalidPublicKeyEncoding(PublicKeyError),
+    /// A serialized scalar (secret key or signature component) is out of
+    /// range.
+    InvalidScalar(ScalarError),
+}
+
+/// Part of a DER-encoded signature.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DerField {
+    /// Tag of the outer sequence.
+    SequenceTag,
+    /// Length of the outer sequence.
+    SequenceLength,
+    /// Tag of the `r` or `s` integer.
+    IntegerTag,
+    /// Length of the `r` or `s` integer.
+    IntegerLength,
+    /// Value of the `r` or `s` integer.
+    IntegerValue,
+    /// Data left over after the outer sequence.
+    TrailingData,
+}
+
+/// Details about a DER decoding failure.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct DerError {
+    /// The field that could not be decoded.
+    pub field: DerField,
+    /// Offset in the input at which decoding failed.
+    pub offset: usize,
+}
+
+/// Details about a public key parsing failure.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PublicKeyError {
+    /// The leading tag byte does not match the key format.
+    InvalidTag(u8),
+    /// A coordinate is not below the field prime.
+    CoordinateOverflow,
+    /// The parity in a hybrid key tag does not match the y coordinate.
+    HybridParityMismatch,
+    /// The point is not on the curve.
+    NotOnCurve,
+}
+
+/// Details about an out-of-range scalar.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ScalarError {
+    /// The value is not below the curve order.
+    Overflow,
+    /// The value is zero.
+    Zero,
 }
 
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
 impl core::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Error::InvalidSignature => write!(f, "Invalid signature"),
             Error::InvalidPublicKey => write!(f, "Invalid public key"),
@@ -24,6 +88,49 @@ impl core::fmt::Display for Error {
             Error::InvalidInputLength => write!(f, "Invalid input length"),
             Error::TweakOutOfRange => write!(f, "Tweak out of range"),
             Error::InvalidAffine => write!(f, "Invalid Affine"),
+            Error::InvalidCiphertext => write!(f, "Invalid ciphertext"),
+            Error::InvalidDer(e) => write!(f, "Invalid DER signature: {}", e),
+            Error::InvalidPublicKeyEncoding(e) => write!(f, "Invalid public key: {}", e),
+            Error::InvalidScalar(e) => write!(f, "Invalid scalar: {}", e),
+        }
+    }
+}
+
+impl core::fmt::Display for DerField {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DerField::SequenceTag => write!(f, "sequence tag"),
+            DerField::SequenceLength => write!(f, "sequence length"),
+            DerField::IntegerTag => write!(f, "integer tag"),
+            DerField::IntegerLength => write!(f, "integer length"),
+            DerField::IntegerValue => write!(f, "integer value"),
+            DerField::TrailingData => write!(f, "trailing data"),
+        }
+    }
+}
+
+impl core::fmt::Display for DerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "bad {} at offset {}", self.field, self.offset)
+    }
+}
+
+impl core::fmt::Display for PublicKeyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PublicKeyError::InvalidTag(tag) => write!(f, "unexpected tag byte {:#04x}", tag),
+            PublicKeyError::CoordinateOverflow => write!(f, "coordinate overflow"),
+            PublicKeyError::HybridParityMismatch => write!(f, "hybrid tag parity mismatch"),
+            PublicKeyError::NotOnCurve => write!(f, "point not on curve"),
+        }
+    }
+}
+
+impl core::fmt::Display for ScalarError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ScalarError::Overflow => write!(f, "overflow"),
+            ScalarError::Zero => write!(f, "zero"),
         }
     }
 }
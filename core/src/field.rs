@@ -2,6 +2,14 @@ use core::{
     cmp::Ordering,
     ops::{Add, AddAssign, Mul, MulAssign},
 };
+use subtle::Choice;
+
+/// All ones if `flag` is set, zero otherwise. `flag` goes through `Choice`,
+/// an optimization barrier, so that the compiler cannot turn a selection
+/// using the mask back into a branch on `flag`.
+fn cmov_mask(flag: bool) -> u32 {
+    (Choice::from(flag as u8).unwrap_u8() as u32).wrapping_neg()
+}
 
 macro_rules! debug_assert_bits {
     ($x: expr, $n: expr) => {
@@ -156,13 +164,9 @@ impl Field {
         debug_assert!(t9 >> 23 == 0);
 
         x = (t9 >> 22)
-            | (if t9 == 0x03fffff { 1 } else { 0 }
-                & if m == 0x3ffffff { 1 } else { 0 }
-                & (if (t1 + 0x40 + ((t0 + 0x3d1) >> 26)) > 0x3ffffff {
-                    1
-                } else {
-                    0
-                }));
+            | ((t9 == 0x03fffff) as u32
+                & (m == 0x3ffffff) as u32
+                & ((t1 + 0x40 + ((t0 + 0x3d1) >> 26)) > 0x3ffffff) as u32);
 
         t0 += x * 0x3d1;
         t1 += x << 6;
@@ -288,13 +292,9 @@ impl Field {
         debug_assert!(t9 >> 23 == 0);
 
         x = (t9 >> 22)
-            | (if t9 == 0x03fffff { 1 } else { 0 }
-                & if m == 0x3ffffff { 1 } else { 0 }
-                & (if (t1 + 0x40 + ((t0 + 0x3d1) >> 26)) > 0x3ffffff {
-                    1
-                } else {
-                    0
-                }));
+            | ((t9 == 0x03fffff) as u32
+                & (m == 0x3ffffff) as u32
+                & ((t1 + 0x40 + ((t0 + 0x3d1) >> 26)) > 0x3ffffff) as u32);
 
         if x > 0 {
             t0 += 0x3d1;
@@ -394,7 +394,8 @@ impl Field {
 
         debug_assert!(t9 >> 23 == 0);
 
-        z0 == 0 || z1 == 0x3ffffff
+        /* Not `||`, which compiles to a branch on the value. */
+        (z0 == 0) | (z1 == 0x3ffffff)
     }
 
     /// Verify whether a field element represents zero i.e. would
@@ -521,7 +522,9 @@ impl Field {
     pub fn clear(&mut self) {
         self.magnitude = 0;
         self.normalized = true;
-        self.n = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        unsafe {
+            core::ptr::write_volatile(&mut self.n, [0u32; 10]);
+        }
     }
 
     /// Set a field element equal to 32-byte big endian value. If
@@ -1638,26 +1641,21 @@ impl Field {
     /// If flag is true, set *r equal to *a; otherwise leave
     /// it. Constant-time.
     pub fn cmov(&mut self, other: &Field, flag: bool) {
-        self.n[0] = if flag { other.n[0] } else { self.n[0] };
-        self.n[1] = if flag { other.n[1] } else { self.n[1] };
-        self.n[2] = if flag { other.n[2] } else { self.n[2] };
-        self.n[3] = if flag { other.n[3] } else { self.n[3] };
-        self.n[4] = if flag { other.n[4] } else { self.n[4] };
-        self.n[5] = if flag { other.n[5] } else { self.n[5] };
-        self.n[6] = if flag { other.n[6] } else { self.n[6] };
-        self.n[7] = if flag { other.n[7] } else { self.n[7] };
-        self.n[8] = if flag { other.n[8] } else { self.n[8] };
-        self.n[9] = if flag { other.n[9] } else { self.n[9] };
-        self.magnitude = if flag {
-            other.magnitude
-        } else {
-            self.magnitude
-        };
-        self.normalized = if flag {
-            other.normalized
-        } else {
-            self.normalized
-        };
+        let mask1 = cmov_mask(flag);
+        let mask0 = !mask1;
+        for i in 0..10 {
+            self.n[i] = (self.n[i] & mask0) | (other.n[i] & mask1);
+        }
+        self.magnitude = (self.magnitude & mask0) | (other.magnitude & mask1);
+        self.normalized =
+            ((self.normalized as u32 & mask0) | (other.normalized as u32 & mask1)) != 0;
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Field {
+    fn zeroize(&mut self) {
+        self.clear();
     }
 }
 
@@ -1793,14 +1791,18 @@ impl FieldStorage {
     }
 
     pub fn cmov(&mut self, other: &FieldStorage, flag: bool) {
-        self.0[0] = if flag { other.0[0] } else { self.0[0] };
-        self.0[1] = if flag { other.0[1] } else { self.0[1] };
-        self.0[2] = if flag { other.0[2] } else { self.0[2] };
-        self.0[3] = if flag { other.0[3] } else { self.0[3] };
-        self.0[4] = if flag { other.0[4] } else { self.0[4] };
-        self.0[5] = if flag { other.0[5] } else { self.0[5] };
-        self.0[6] = if flag { other.0[6] } else { self.0[6] };
-        self.0[7] = if flag { other.0[7] } else { self.0[7] };
+        let mask1 = cmov_mask(flag);
+        let mask0 = !mask1;
+        for i in 0..8 {
+            self.0[i] = (self.0[i] & mask0) | (other.0[i] & mask1);
+        }
+    }
+
+    /// Clear a field storage to prevent the leak of sensitive data.
+    pub fn clear(&mut self) {
+        unsafe {
+            core::ptr::write_volatile(&mut self.0, [0u32; 8]);
+        }
     }
 }
 
@@ -475,7 +475,7 @@ impl Jacobian {
         let mut m_alt = u2.neg(1);
         let tt = u1 * m_alt;
         rr += tt;
-        let degenerate = m.normalizes_to_zero() && rr.normalizes_to_zero();
+        let degenerate = m.normalizes_to_zero() & rr.normalizes_to_zero();
         let mut rr_alt = s1;
         rr_alt.mul_int(2);
         m_alt += u1;
@@ -490,17 +490,7 @@ impl Jacobian {
         n.cmov(&m, degenerate);
         t = rr_alt.sqr();
         self.z = a.z * m_alt;
-        let infinity = {
-            let p = self.z.normalizes_to_zero();
-            let q = a.infinity;
-
-            match (p, q) {
-                (true, true) => false,
-                (true, false) => true,
-                (false, true) => false,
-                (false, false) => false,
-            }
-        };
+        let infinity = self.z.normalizes_to_zero() & !a.infinity;
         self.z.mul_int(2);
         q = q.neg(1);
         t += q;
@@ -710,4 +700,31 @@ impl AffineStorage {
         self.x.cmov(&a.x, flag);
         self.y.cmov(&a.y, flag);
     }
+
+    /// Clear an affine storage to prevent leaking sensitive information.
+    pub fn clear(&mut self) {
+        self.x.clear();
+        self.y.clear();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Affine {
+    fn zeroize(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Jacobian {
+    fn zeroize(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for AffineStorage {
+    fn zeroize(&mut self) {
+        self.clear();
+    }
 }
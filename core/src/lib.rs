@@ -1,5 +1,7 @@
 //! Core libraries for libsecp256k1
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
 #[allow(
     clippy::cast_ptr_alignment,
     clippy::identity_op,
@@ -30,7 +32,7 @@ mod ecmult;
 mod error;
 mod scalar;
 
-pub use crate::error::Error;
+pub use crate::error::{DerError, DerField, Error, PublicKeyError, ScalarError};
 
 /// Curve related structure
 pub mod curve {
@@ -57,11 +59,14 @@ pub mod util {
     pub const FULL_PUBLIC_KEY_SIZE: usize = 65;
     pub const COMPRESSED_PUBLIC_KEY_SIZE: usize = 33;
     pub const SIGNATURE_SIZE: usize = 64;
+    pub const RECOVERABLE_SIGNATURE_SIZE: usize = 65;
     pub const DER_MAX_SIGNATURE_SIZE: usize = 72;
 
     pub use crate::{
         ecmult::{
-            odd_multiples_table, ECMULT_TABLE_SIZE_A, ECMULT_TABLE_SIZE_G, WINDOW_A, WINDOW_G,
+            inv_all_var, odd_multiples_table, set_all_gej_var, ECMULT_GEN_PREC_BITS,
+            ECMULT_GEN_PREC_G, ECMULT_GEN_PREC_N, ECMULT_TABLE_SIZE_A, ECMULT_TABLE_SIZE_G,
+            WINDOW_A, WINDOW_G,
         },
         group::{globalz_set_table_gej, set_table_gej_var, AFFINE_INFINITY, JACOBIAN_INFINITY},
     };
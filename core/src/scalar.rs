@@ -127,29 +127,29 @@ impl Scalar {
     pub fn cadd_bit(&mut self, mut bit: usize, flag: bool) {
         let mut t: u64;
         debug_assert!(bit < 256);
-        bit += if flag { 0 } else { usize::max_value() } & 0x100;
-        t = (self.0[0] as u64) + ((if (bit >> 5) == 0 { 1 } else { 0 }) << (bit & 0x1F));
+        bit += (flag as usize).wrapping_sub(1) & 0x100;
+        t = (self.0[0] as u64) + ((((bit >> 5) == 0) as u64) << (bit & 0x1F));
         self.0[0] = (t & 0xFFFFFFFF) as u32;
         t >>= 32;
-        t += (self.0[1] as u64) + ((if (bit >> 5) == 1 { 1 } else { 0 }) << (bit & 0x1F));
+        t += (self.0[1] as u64) + ((((bit >> 5) == 1) as u64) << (bit & 0x1F));
         self.0[1] = (t & 0xFFFFFFFF) as u32;
         t >>= 32;
-        t += (self.0[2] as u64) + ((if (bit >> 5) == 2 { 1 } else { 0 }) << (bit & 0x1F));
+        t += (self.0[2] as u64) + ((((bit >> 5) == 2) as u64) << (bit & 0x1F));
         self.0[2] = (t & 0xFFFFFFFF) as u32;
         t >>= 32;
-        t += (self.0[3] as u64) + ((if (bit >> 5) == 3 { 1 } else { 0 }) << (bit & 0x1F));
+        t += (self.0[3] as u64) + ((((bit >> 5) == 3) as u64) << (bit & 0x1F));
         self.0[3] = (t & 0xFFFFFFFF) as u32;
         t >>= 32;
-        t += (self.0[4] as u64) + ((if (bit >> 5) == 4 { 1 } else { 0 }) << (bit & 0x1F));
+        t += (self.0[4] as u64) + ((((bit >> 5) == 4) as u64) << (bit & 0x1F));
         self.0[4] = (t & 0xFFFFFFFF) as u32;
         t >>= 32;
-        t += (self.0[5] as u64) + ((if (bit >> 5) == 5 { 1 } else { 0 }) << (bit & 0x1F));
+        t += (self.0[5] as u64) + ((((bit >> 5) == 5) as u64) << (bit & 0x1F));
         self.0[5] = (t & 0xFFFFFFFF) as u32;
         t >>= 32;
-        t += (self.0[6] as u64) + ((if (bit >> 5) == 6 { 1 } else { 0 }) << (bit & 0x1F));
+        t += (self.0[6] as u64) + ((((bit >> 5) == 6) as u64) << (bit & 0x1F));
         self.0[6] = (t & 0xFFFFFFFF) as u32;
         t >>= 32;
-        t += (self.0[7] as u64) + ((if (bit >> 5) == 7 { 1 } else { 0 }) << (bit & 0x1F));
+        t += (self.0[7] as u64) + ((((bit >> 5) == 7) as u64) << (bit & 0x1F));
         self.0[7] = (t & 0xFFFFFFFF) as u32;
         debug_assert!((t >> 32) == 0);
         debug_assert!(!bool::from(self.check_overflow()));
@@ -316,9 +316,9 @@ macro_rules! define_ops {
                 let mut th = (t >> 32) as u32;
                 let tl = t as u32;
                 $c0 = $c0.wrapping_add(tl);
-                th = th.wrapping_add(if $c0 < tl { 1 } else { 0 });
+                th = th.wrapping_add(($c0 < tl) as u32);
                 $c1 = $c1.wrapping_add(th);
-                $c2 = $c2.wrapping_add(if $c1 < th { 1 } else { 0 });
+                $c2 = $c2.wrapping_add(($c1 < th) as u32);
                 debug_assert!($c1 >= th || $c2 != 0);
             };
         }
@@ -332,7 +332,7 @@ macro_rules! define_ops {
                 let mut th = (t >> 32) as u32;
                 let tl = t as u32;
                 $c0 = $c0.wrapping_add(tl);
-                th = th.wrapping_add(if $c0 < tl { 1 } else { 0 });
+                th = th.wrapping_add(($c0 < tl) as u32);
                 $c1 = $c1.wrapping_add(th);
                 debug_assert!($c1 >= th);
             };
@@ -347,16 +347,16 @@ macro_rules! define_ops {
                 let th = (t >> 32) as u32;
                 let tl = t as u32;
                 let mut th2 = th.wrapping_add(th);
-                $c2 = $c2.wrapping_add(if th2 < th { 1 } else { 0 });
+                $c2 = $c2.wrapping_add((th2 < th) as u32);
                 debug_assert!(th2 >= th || $c2 != 0);
                 let tl2 = tl.wrapping_add(tl);
-                th2 = th2.wrapping_add(if tl2 < tl { 1 } else { 0 });
+                th2 = th2.wrapping_add((tl2 < tl) as u32);
                 $c0 = $c0.wrapping_add(tl2);
-                th2 = th2.wrapping_add(if $c0 < tl2 { 1 } else { 0 });
-                $c2 = $c2.wrapping_add(if $c0 < tl2 && th2 == 0 { 1 } else { 0 });
+                th2 = th2.wrapping_add(($c0 < tl2) as u32);
+                $c2 = $c2.wrapping_add((($c0 < tl2) & (th2 == 0)) as u32);
                 debug_assert!($c0 >= tl2 || th2 != 0 || $c2 != 0);
                 $c1 = $c1.wrapping_add(th2);
-                $c2 = $c2.wrapping_add(if $c1 < th2 { 1 } else { 0 });
+                $c2 = $c2.wrapping_add(($c1 < th2) as u32);
                 debug_assert!($c1 >= th2 || $c2 != 0);
             };
         }
@@ -366,9 +366,9 @@ macro_rules! define_ops {
             ($a: expr) => {
                 let a = $a;
                 $c0 = $c0.wrapping_add(a);
-                let over = if $c0 < a { 1 } else { 0 };
+                let over = ($c0 < a) as u32;
                 $c1 = $c1.wrapping_add(over);
-                $c2 = $c2.wrapping_add(if $c1 < over { 1 } else { 0 });
+                $c2 = $c2.wrapping_add(($c1 < over) as u32);
             };
         }
 
@@ -377,7 +377,7 @@ macro_rules! define_ops {
             ($a: expr) => {
                 let a = $a;
                 $c0 = $c0.wrapping_add(a);
-                $c1 = $c1.wrapping_add(if $c0 < a { 1 } else { 0 });
+                $c1 = $c1.wrapping_add(($c0 < a) as u32);
                 debug_assert!($c1 != 0 || $c0 >= a);
                 debug_assert!($c2 == 0);
             };
@@ -926,6 +926,13 @@ impl Scalar {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Scalar {
+    fn zeroize(&mut self) {
+        self.clear();
+    }
+}
+
 impl Default for Scalar {
     fn default() -> Scalar {
         Scalar([0u32; 8])
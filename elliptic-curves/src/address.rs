@@ -0,0 +1,234 @@
+use std::str::FromStr;
+
+use crate::{
+    bech32::decode_segwit_address,
+    helper::{decode_base58_checksum, DecodeError},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Signet,
+    Regtest,
+}
+
+impl Network {
+    /// Base58Check version byte of P2PKH addresses.
+    pub fn p2pkh_prefix(self) -> u8 {
+        match self {
+            Network::Mainnet => 0x00,
+            _ => 0x6f,
+        }
+    }
+
+    /// Base58Check version byte of P2SH addresses.
+    pub fn p2sh_prefix(self) -> u8 {
+        match self {
+            Network::Mainnet => 0x05,
+            _ => 0xc4,
+        }
+    }
+
+    /// Human-readable part of SegWit addresses.
+    pub fn hrp(self) -> &'static str {
+        match self {
+            Network::Mainnet => "bc",
+            Network::Testnet | Network::Signet => "tb",
+            Network::Regtest => "bcrt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressType {
+    /// Pay to public key hash.
+    P2pkh,
+    /// Pay to script hash.
+    P2sh,
+    /// Pay to witness public key hash.
+    P2wpkh,
+    /// Pay to witness script hash.
+    P2wsh,
+    /// Pay to taproot.
+    P2tr,
+}
+
+/// The witness version and program of a SegWit address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WitnessProgram {
+    pub version: u8,
+    pub program: Vec<u8>,
+}
+
+/// A decoded Base58Check or SegWit address.
+///
+/// Testnet, signet and regtest share their Base58Check version bytes, and
+/// testnet and signet share their SegWit prefix, so these parse as
+/// `Network::Testnet`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Address {
+    pub network: Network,
+    pub address_type: AddressType,
+    /// hash160 of the public key or of the redeem script, for P2PKH and P2SH
+    /// addresses.
+    pub hash160: Option<[u8; 20]>,
+    /// The witness program of SegWit addresses.
+    pub witness_program: Option<WitnessProgram>,
+}
+
+impl Address {
+    pub fn parse(s: &str) -> Result<Self, DecodeError> {
+        let lower = s.to_ascii_lowercase();
+        for network in [Network::Mainnet, Network::Testnet, Network::Regtest] {
+            if lower.starts_with(&format!("{}1", network.hrp())) {
+                return Self::parse_segwit(s, network);
+            }
+        }
+
+        let payload = decode_base58_checksum(s)?;
+        if payload.len() != 21 {
+            return Err(DecodeError::InvalidLength(payload.len()));
+        }
+
+        let (network, address_type) = match payload[0] {
+            0x00 => (Network::Mainnet, AddressType::P2pkh),
+            0x05 => (Network::Mainnet, AddressType::P2sh),
+            0x6f => (Network::Testnet, AddressType::P2pkh),
+            0xc4 => (Network::Testnet, AddressType::P2sh),
+            version => return Err(DecodeError::InvalidVersion(version)),
+        };
+
+        let mut hash160 = [0u8; 20];
+        hash160.copy_from_slice(&payload[1..]);
+
+        Ok(Self {
+            network,
+            address_type,
+            hash160: Some(hash160),
+            witness_program: None,
+        })
+    }
+
+    fn parse_segwit(s: &str, network: Network) -> Result<Self, DecodeError> {
+        let (version, program) = decode_segwit_address(network.hrp(), s)?;
+        let address_type = match (version, program.len()) {
+            (0, 20) => AddressType::P2wpkh,
+            (0, 32) => AddressType::P2wsh,
+            (1, 32) => AddressType::P2tr,
+            (1, len) => return Err(DecodeError::InvalidLength(len)),
+            (version, _) => return Err(DecodeError::InvalidVersion(version)),
+        };
+
+        Ok(Self {
+            network,
+            address_type,
+            hash160: None,
+            witness_program: Some(WitnessProgram { version, program }),
+        })
+    }
+}
+
+impl FromStr for Address {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use num_traits::FromPrimitive;
+
+    use super::{Address, AddressType, Network, WitnessProgram};
+    use crate::{helper::DecodeError, private_key::PrivateKey};
+
+    #[test]
+    fn test_parse_address() {
+        let point = PrivateKey::new(BigUint::from_u16(5002).unwrap()).point;
+        for (compressed, testnet) in [(false, true), (true, true), (false, false), (true, false)] {
+            let address = Address::parse(&point.address(compressed, testnet)).unwrap();
+            assert_eq!(address.address_type, AddressType::P2pkh);
+            assert_eq!(
+                address.network,
+                if testnet {
+                    Network::Testnet
+                } else {
+                    Network::Mainnet
+                }
+            );
+            assert_eq!(address.hash160.unwrap()[..], point.hash160(compressed)[..]);
+            assert_eq!(address.witness_program, None);
+        }
+
+        let address: Address = "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy".parse().unwrap();
+        assert_eq!(address.network, Network::Mainnet);
+        assert_eq!(address.address_type, AddressType::P2sh);
+        assert_eq!(
+            hex::encode(address.hash160.unwrap()),
+            "b472a266d0bd89c13706a4132ccfb16f7c3b9fcb"
+        );
+
+        assert_eq!(
+            Address::parse("3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLz"),
+            Err(DecodeError::InvalidChecksum)
+        );
+        // A WIF is not an address.
+        assert_eq!(
+            Address::parse("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgiuQJv1h8Ytr2S53a"),
+            Err(DecodeError::InvalidLength(34))
+        );
+    }
+
+    #[test]
+    fn test_parse_segwit_address() {
+        let point = PrivateKey::new(BigUint::from_u16(5002).unwrap()).point;
+        for network in [Network::Mainnet, Network::Testnet, Network::Regtest] {
+            let address = Address::parse(&point.p2wpkh_address(network)).unwrap();
+            assert_eq!(address.network, network);
+            assert_eq!(address.address_type, AddressType::P2wpkh);
+            assert_eq!(address.hash160, None);
+            assert_eq!(
+                address.witness_program,
+                Some(WitnessProgram {
+                    version: 0,
+                    program: point.hash160(true),
+                })
+            );
+
+            let address = Address::parse(&point.p2tr_address(network).unwrap()).unwrap();
+            assert_eq!(address.network, network);
+            assert_eq!(address.address_type, AddressType::P2tr);
+            let witness_program = address.witness_program.unwrap();
+            assert_eq!(witness_program.version, 1);
+            assert_eq!(witness_program.program.len(), 32);
+
+            let address = Address::parse(&point.p2sh_p2wpkh_address(network)).unwrap();
+            assert_eq!(address.address_type, AddressType::P2sh);
+            assert!(address.hash160.is_some());
+        }
+        assert_eq!(
+            Address::parse(&point.p2wpkh_address(Network::Signet))
+                .unwrap()
+                .network,
+            Network::Testnet
+        );
+
+        let address: Address = "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7"
+            .parse()
+            .unwrap();
+        assert_eq!(address.address_type, AddressType::P2wsh);
+
+        // Witness version 2 is valid bech32m but has no address type here.
+        assert_eq!(
+            Address::parse("bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs"),
+            Err(DecodeError::InvalidVersion(2))
+        );
+        assert_eq!(
+            Address::parse("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5"),
+            Err(DecodeError::InvalidChecksum)
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Bech32 (BIP173) and bech32m (BIP350) encoding, and SegWit addresses.
+
+use crate::helper::DecodeError;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc830a3;
+const CHECKSUM_LENGTH: usize = 6;
+const MAX_LENGTH: usize = 90;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// BIP173, used by witness version 0.
+    Bech32,
+    /// BIP350, used by witness versions 1 to 16.
+    Bech32m,
+}
+
+impl Variant {
+    fn constant(self) -> u32 {
+        match self {
+            Variant::Bech32 => BECH32_CONST,
+            Variant::Bech32m => BECH32M_CONST,
+        }
+    }
+}
+
+fn polymod(values: &[u8]) -> u32 {
+    let mut chk = 1u32;
+    for &v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ v as u32;
+        for (i, g) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut result: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    result.push(0);
+    result.extend(hrp.bytes().map(|b| b & 0x1f));
+    result
+}
+
+/// Encode 5-bit `data` under the human-readable part `hrp`.
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> String {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0u8; CHECKSUM_LENGTH]);
+    let checksum = polymod(&values) ^ variant.constant();
+
+    let mut result = String::from(hrp);
+    result.push('1');
+    for &d in data {
+        result.push(CHARSET[d as usize] as char);
+    }
+    for i in 0..CHECKSUM_LENGTH {
+        let d = (checksum >> (5 * (5 - i))) & 0x1f;
+        result.push(CHARSET[d as usize] as char);
+    }
+    result
+}
+
+/// Decode a bech32 or bech32m string into its lowercase human-readable
+/// part and 5-bit data, without the checksum.
+pub fn decode(s: &str) -> Result<(String, Vec<u8>, Variant), DecodeError> {
+    if s.len() > MAX_LENGTH {
+        return Err(DecodeError::InvalidLength(s.len()));
+    }
+    if s.chars().any(|c| c.is_ascii_lowercase()) && s.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err(DecodeError::MixedCase);
+    }
+    let s = s.to_ascii_lowercase();
+
+    let pos = s.rfind('1').ok_or(DecodeError::InvalidHrp)?;
+    let (hrp, data) = (&s[..pos], &s[pos + 1..]);
+    if hrp.is_empty() || hrp.bytes().any(|b| !(33..=126).contains(&b)) {
+        return Err(DecodeError::InvalidHrp);
+    }
+    if data.len() < CHECKSUM_LENGTH {
+        return Err(DecodeError::InvalidLength(data.len()));
+    }
+
+    let data = data
+        .chars()
+        .map(|c| {
+            CHARSET
+                .iter()
+                .position(|&b| b as char == c)
+                .map(|d| d as u8)
+                .ok_or(DecodeError::InvalidCharacter(c))
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(&data);
+    let variant = match polymod(&values) {
+        BECH32_CONST => Variant::Bech32,
+        BECH32M_CONST => Variant::Bech32m,
+        _ => return Err(DecodeError::InvalidChecksum),
+    };
+
+    Ok((
+        hrp.to_string(),
+        data[..data.len() - CHECKSUM_LENGTH].to_vec(),
+        variant,
+    ))
+}
+
+/// Regroup `data` from `from`-bit to `to`-bit groups. Without `pad`, the
+/// leftover bits must be zero padding shorter than `from` bits.
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, DecodeError> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let max = (1u32 << to) - 1;
+    let mut result = Vec::new();
+
+    for &v in data {
+        if (v as u32) >> from != 0 {
+            return Err(DecodeError::InvalidPadding);
+        }
+        acc = (acc << from) | v as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            result.push(((acc >> bits) & max) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            result.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & max) != 0 {
+        return Err(DecodeError::InvalidPadding);
+    }
+
+    Ok(result)
+}
+
+/// Encode a SegWit address. Version 0 uses bech32, later versions bech32m.
+pub fn encode_segwit_address(hrp: &str, version: u8, program: &[u8]) -> String {
+    let variant = if version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    };
+
+    let mut data = vec![version];
+    data.extend(convert_bits(program, 8, 5, true).unwrap());
+    encode(hrp, &data, variant)
+}
+
+/// Decode a SegWit address for `hrp` into its witness version and program.
+pub fn decode_segwit_address(hrp: &str, address: &str) -> Result<(u8, Vec<u8>), DecodeError> {
+    let (decoded_hrp, data, variant) = decode(address)?;
+    if decoded_hrp != hrp {
+        return Err(DecodeError::InvalidHrp);
+    }
+
+    let (&version, data) = data.split_first().ok_or(DecodeError::InvalidLength(0))?;
+    if version > 16 {
+        return Err(DecodeError::InvalidVersion(version));
+    }
+    let program = convert_bits(data, 5, 8, false)?;
+    if program.len() < 2
+        || program.len() > 40
+        || (version == 0 && ![20, 32].contains(&program.len()))
+    {
+        return Err(DecodeError::InvalidLength(program.len()));
+    }
+
+    let expected = if version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    };
+    if variant != expected {
+        return Err(DecodeError::InvalidVariant);
+    }
+
+    Ok((version, program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, decode_segwit_address, encode_segwit_address, Variant};
+    use crate::helper::DecodeError;
+
+    #[test]
+    fn test_decode() {
+        for (s, variant) in [
+            ("A12UEL5L", Variant::Bech32),
+            ("an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs", Variant::Bech32),
+            ("split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w", Variant::Bech32),
+            ("A1LQFN3A", Variant::Bech32m),
+            ("abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx", Variant::Bech32m),
+            ("?1v759aa", Variant::Bech32m),
+        ] {
+            assert_eq!(decode(s).unwrap().2, variant, "{}", s);
+        }
+
+        assert_eq!(decode("A1G7SGD8"), Err(DecodeError::InvalidChecksum));
+        assert_eq!(decode("pzry9x0s0muk"), Err(DecodeError::InvalidHrp));
+        assert_eq!(decode("1pzry9x0s0muk"), Err(DecodeError::InvalidHrp));
+        assert_eq!(decode("li1dgmt3"), Err(DecodeError::InvalidLength(5)));
+        assert_eq!(decode("A1Lqfn3a"), Err(DecodeError::MixedCase));
+        assert_eq!(decode("x1b4n0q5v"), Err(DecodeError::InvalidCharacter('b')));
+    }
+
+    #[test]
+    fn test_segwit_address() {
+        for (address, hrp, version, program) in [
+            (
+                "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+                "bc",
+                0,
+                "751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                "tb",
+                0,
+                "1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                "bc",
+                1,
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            ),
+            (
+                "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+                "tb",
+                1,
+                "000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+            ),
+        ] {
+            let program = hex::decode(program).unwrap();
+            assert_eq!(
+                decode_segwit_address(hrp, address).unwrap(),
+                (version, program.clone())
+            );
+            assert_eq!(
+                encode_segwit_address(hrp, version, &program),
+                address.to_lowercase()
+            );
+        }
+
+        // Wrong hrp.
+        assert_eq!(
+            decode_segwit_address("tb", "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            Err(DecodeError::InvalidHrp)
+        );
+        // Version 0 with a bech32m checksum.
+        assert_eq!(
+            decode_segwit_address("bc", "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh"),
+            Err(DecodeError::InvalidVariant)
+        );
+        // Version 1 with a bech32 checksum.
+        assert_eq!(
+            decode_segwit_address(
+                "tb",
+                "tb1q0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq24jc47"
+            ),
+            Err(DecodeError::InvalidVariant)
+        );
+        // Invalid program length for version 0.
+        assert_eq!(
+            decode_segwit_address("bc", "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P"),
+            Err(DecodeError::InvalidLength(16))
+        );
+    }
+}
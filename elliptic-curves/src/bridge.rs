@@ -0,0 +1,202 @@
+//! Conversions between this crate's types and those of libsecp256k1.
+//!
+//! Conversions into libsecp256k1 fail for values it rejects: the point at
+//! infinity or points of other curves, out of range secrets, and signature
+//! values not below the group order.
+
+use finite_fields::P;
+use libsecp256k1::{Error, PublicKey, SecretKey};
+use num_bigint::BigUint;
+use num_traits::Num;
+
+use crate::{helper::to_bytes32, private_key::PrivateKey, signature::Signature, Point};
+
+impl From<PublicKey> for Point {
+    fn from(key: PublicKey) -> Self {
+        Point::try_parse(&key.serialize()).unwrap()
+    }
+}
+
+impl TryFrom<&Point> for PublicKey {
+    type Error = Error;
+
+    fn try_from(point: &Point) -> Result<Self, Error> {
+        if point.is_infinity() || point.a.prime != BigUint::from_str_radix(P, 16).unwrap() {
+            return Err(Error::InvalidPublicKey);
+        }
+        PublicKey::parse(&point.sec(false).try_into().unwrap())
+    }
+}
+
+impl From<SecretKey> for PrivateKey {
+    fn from(key: SecretKey) -> Self {
+        PrivateKey::new(BigUint::from_bytes_be(&key.serialize()))
+    }
+}
+
+impl TryFrom<&PrivateKey> for SecretKey {
+    type Error = Error;
+
+    fn try_from(key: &PrivateKey) -> Result<Self, Error> {
+        if key.secret.bits() > 256 {
+            return Err(Error::InvalidSecretKey);
+        }
+        SecretKey::parse(&to_bytes32(&key.secret))
+    }
+}
+
+impl From<libsecp256k1::Signature> for Signature {
+    fn from(sig: libsecp256k1::Signature) -> Self {
+        let bytes = sig.serialize();
+        Signature::new(
+            BigUint::from_bytes_be(&bytes[..32]),
+            BigUint::from_bytes_be(&bytes[32..]),
+        )
+    }
+}
+
+impl TryFrom<&Signature> for libsecp256k1::Signature {
+    type Error = Error;
+
+    fn try_from(sig: &Signature) -> Result<Self, Error> {
+        if sig.r.bits() > 256 || sig.s.bits() > 256 {
+            return Err(Error::InvalidSignature);
+        }
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&to_bytes32(&sig.r));
+        bytes[32..].copy_from_slice(&to_bytes32(&sig.s));
+        libsecp256k1::Signature::parse_standard(&bytes)
+    }
+}
+
+#[cfg(any(feature = "fast", test))]
+fn message(z: &BigUint) -> libsecp256k1::Message {
+    let n = BigUint::from_str_radix(crate::N, 16).unwrap();
+    libsecp256k1::Message::parse(&to_bytes32(&(z % n)))
+}
+
+/// Sign with libsecp256k1. Both use RFC6979 and low S values, so this gives
+/// the same signature as the `BigUint` implementation. `None` for secrets
+/// that libsecp256k1 rejects: zero, and n or above.
+#[cfg(feature = "fast")]
+pub(crate) fn sign(key: &PrivateKey, z: &BigUint) -> Option<(Signature, u8)> {
+    let seckey = SecretKey::try_from(key).ok()?;
+    let (sig, recid) = libsecp256k1::sign(&message(z), &seckey);
+    Some((sig.into(), recid.into()))
+}
+
+/// Verify with libsecp256k1. High S values are normalized first, since the
+/// `BigUint` implementation accepts them.
+#[cfg(feature = "fast")]
+pub(crate) fn verify(point: &Point, z: &BigUint, sig: &Signature) -> bool {
+    let (pubkey, mut sig) = match (
+        PublicKey::try_from(point),
+        libsecp256k1::Signature::try_from(sig),
+    ) {
+        (Ok(pubkey), Ok(sig)) => (pubkey, sig),
+        _ => return false,
+    };
+    if sig.r.is_zero() || sig.s.is_zero() {
+        return false;
+    }
+    sig.normalize_s();
+    libsecp256k1::verify(&message(z), &sig, &pubkey)
+}
+
+#[cfg(test)]
+mod tests {
+    use libsecp256k1::{Error, PublicKey, ScalarError, SecretKey};
+    use num_bigint::{BigUint, RandBigInt};
+    use num_traits::{Num, One, Zero};
+    use rand::{rngs::OsRng, thread_rng, RngCore};
+
+    use super::message;
+    use crate::{private_key::PrivateKey, signature::Signature, Point, N};
+
+    #[test]
+    fn test_key_conversions() {
+        for _ in 0..10 {
+            let seckey = SecretKey::random(&mut OsRng);
+            let pubkey = PublicKey::from_secret_key(&seckey);
+
+            let key = PrivateKey::from(seckey);
+            assert_eq!(key.point, Point::from(pubkey));
+            assert_eq!(SecretKey::try_from(&key).unwrap(), seckey);
+            assert_eq!(PublicKey::try_from(&key.point).unwrap(), pubkey);
+        }
+
+        let n = BigUint::from_str_radix(N, 16).unwrap();
+        let infinity = Point::get_point_g().rmul(n.clone());
+        assert_eq!(PublicKey::try_from(&infinity), Err(Error::InvalidPublicKey));
+        let key = PrivateKey {
+            secret: n,
+            point: infinity,
+        };
+        // n fits in 32 bytes, so the parser rejects it.
+        assert_eq!(
+            SecretKey::try_from(&key),
+            Err(Error::InvalidScalar(ScalarError::Overflow))
+        );
+    }
+
+    #[test]
+    fn test_signature_conversions() {
+        let mut rng = thread_rng();
+        let n = BigUint::from_str_radix(N, 16).unwrap();
+        for _ in 0..10 {
+            let sig = Signature::new(rng.gen_biguint_below(&n), rng.gen_biguint_below(&n));
+            let converted = libsecp256k1::Signature::try_from(&sig).unwrap();
+            assert_eq!(Signature::from(converted), sig);
+        }
+
+        let sig = Signature::new(n.clone(), BigUint::one());
+        assert!(libsecp256k1::Signature::try_from(&sig).is_err());
+        let sig = Signature::new(BigUint::one(), BigUint::one() << 256);
+        assert!(libsecp256k1::Signature::try_from(&sig).is_err());
+    }
+
+    #[test]
+    fn test_cross_sign_verify() {
+        for _ in 0..10 {
+            let seckey = SecretKey::random(&mut OsRng);
+            let pubkey = PublicKey::from_secret_key(&seckey);
+            let key = PrivateKey::from(seckey);
+
+            let mut z = [0u8; 32];
+            OsRng.fill_bytes(&mut z);
+            let z = BigUint::from_bytes_be(&z);
+
+            // The same RFC6979 signature on both sides.
+            let (sig, recid) = libsecp256k1::sign(&message(&z), &seckey);
+            let (expected, expected_recid) = key.sign_with_k(z.clone(), key.deterministic_k(&z));
+            assert_eq!(Signature::from(sig), expected);
+            let recid: u8 = recid.into();
+            assert_eq!(recid, expected_recid);
+            assert_eq!(
+                key.sign_recoverable(z.clone()),
+                (expected.clone(), expected_recid)
+            );
+            assert_eq!(
+                Point::recover(&z, &expected, expected_recid).unwrap(),
+                Point::from(pubkey)
+            );
+
+            let sig = key.sign_with_rng(z.clone(), &mut thread_rng());
+            assert!(libsecp256k1::verify(
+                &message(&z),
+                &libsecp256k1::Signature::try_from(&sig).unwrap(),
+                &pubkey
+            ));
+            assert!(key.point.verify(&z, &sig));
+
+            // High S values verify in both implementations of `verify`.
+            let n = BigUint::from_str_radix(N, 16).unwrap();
+            let high = Signature::new(sig.r.clone(), &n - &sig.s);
+            assert!(key.point.verify(&z, &high));
+            assert!(!key.point.verify(&(z + BigUint::one()), &sig));
+            assert!(!key
+                .point
+                .verify(&BigUint::zero(), &Signature::new(sig.r, BigUint::zero())));
+        }
+    }
+}
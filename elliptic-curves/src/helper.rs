@@ -1,4 +1,5 @@
 use std::{
+    fmt,
     io::{Error, Read},
     thread::current,
 };
@@ -38,6 +39,29 @@ pub fn hash256(s: &[u8]) -> [u8; 32] {
     result
 }
 
+/// BIP340 tagged hash, `sha256(sha256(tag) || sha256(tag) || msg)`.
+pub fn tagged_hash(tag: &str, msg: &[u8]) -> [u8; 32] {
+    let tag = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update(tag);
+    hasher.update(msg);
+
+    let mut result = [0u8; 32];
+    result.copy_from_slice(&hasher.finalize());
+    result
+}
+
+/// Big-endian encoding of `n`, left padded to 32 bytes.
+pub fn to_bytes32(n: &BigUint) -> [u8; 32] {
+    let bytes = n.to_bytes_be();
+    let mut res = [0u8; 32];
+    if bytes.len() <= 32 {
+        res[32 - bytes.len()..].copy_from_slice(&bytes);
+    }
+    res
+}
+
 pub fn encode_base58(s: &Vec<u8>) -> String {
     let mut result = String::new();
     let mut count = 0;
@@ -77,14 +101,83 @@ pub fn encode_base58_checksum(b: &mut Vec<u8>) -> String {
     encode_base58(b)
 }
 
-// pub fn decode_base58(s: &Vec<u8>) -> Vec<u8> {
-//     let mut num = 0;
-//
-//     for c in s {
-//         num *= 58;
-//         num += BASE58_ALPHABET.chars().
-//     }
-// }
+/// Errors when decoding Base58, bech32, WIF, addresses and DER signatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// Character outside of the Base58 or bech32 alphabet.
+    InvalidCharacter(char),
+    /// The Base58Check or bech32 checksum does not match.
+    InvalidChecksum,
+    /// Unexpected payload length.
+    InvalidLength(usize),
+    /// Unknown version byte or witness version.
+    InvalidVersion(u8),
+    /// The decoded secret is zero or not below the group order.
+    InvalidSecret,
+    /// A bech32 string mixing upper and lower case.
+    MixedCase,
+    /// Missing or unexpected bech32 human-readable part.
+    InvalidHrp,
+    /// Non-zero or overlong padding when regrouping bech32 data.
+    InvalidPadding,
+    /// bech32 used where bech32m is required, or the other way around.
+    InvalidVariant,
+    /// Malformed DER signature.
+    InvalidDer,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidCharacter(c) => write!(f, "invalid character {:?}", c),
+            DecodeError::InvalidChecksum => write!(f, "invalid checksum"),
+            DecodeError::InvalidLength(len) => write!(f, "invalid payload length {}", len),
+            DecodeError::InvalidVersion(v) => write!(f, "invalid version byte {:#04x}", v),
+            DecodeError::InvalidSecret => write!(f, "secret out of range"),
+            DecodeError::MixedCase => write!(f, "mixed case"),
+            DecodeError::InvalidHrp => write!(f, "invalid human-readable part"),
+            DecodeError::InvalidPadding => write!(f, "invalid padding"),
+            DecodeError::InvalidVariant => write!(f, "invalid bech32 variant"),
+            DecodeError::InvalidDer => write!(f, "invalid DER signature"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+pub fn decode_base58(s: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut num = BigUint::zero();
+    for c in s.chars() {
+        let digit = BASE58_ALPHABET
+            .find(c)
+            .ok_or(DecodeError::InvalidCharacter(c))?;
+        num = num * 58u8 + digit;
+    }
+
+    // Each leading '1' stands for a zero byte.
+    let count = s.chars().take_while(|&c| c == '1').count();
+    let mut result = vec![0u8; count];
+    if !num.is_zero() {
+        result.extend(num.to_bytes_be());
+    }
+
+    Ok(result)
+}
+
+/// Decode Base58Check, returning the payload without its checksum.
+pub fn decode_base58_checksum(s: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut b = decode_base58(s)?;
+    if b.len() < 4 {
+        return Err(DecodeError::InvalidLength(b.len()));
+    }
+
+    let checksum = b.split_off(b.len() - 4);
+    if hash256(&b)[..4] != checksum[..] {
+        return Err(DecodeError::InvalidChecksum);
+    }
+
+    Ok(b)
+}
 
 pub fn little_endian_bytes_to_u64(bytes: &[u8]) -> u64 {
     let mut result = 0;
@@ -229,7 +322,10 @@ pub fn merkle_parent_level(hashes: &mut Vec<Option<Vec<u8>>>) -> Vec<Option<Vec<
 mod tests {
     use hex::{FromHex, ToHex};
 
-    use super::{encode_base58, merkle_parent, merkle_parent_level};
+    use super::{
+        decode_base58, decode_base58_checksum, encode_base58, encode_base58_checksum,
+        merkle_parent, merkle_parent_level, DecodeError,
+    };
 
     #[test]
     fn test_encode_base58() {
@@ -252,6 +348,45 @@ mod tests {
         assert_eq!(res, "EQJsjkd6JaGwxrjEhfeqPenqHwrBmPQZjJGNSCHBkcF7");
     }
 
+    #[test]
+    fn test_decode_base58() {
+        for h in [
+            "7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d",
+            "00eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c",
+            "0000",
+            "",
+        ] {
+            let s = hex::decode(h).unwrap();
+            assert_eq!(decode_base58(&encode_base58(&s)).unwrap(), s);
+        }
+
+        assert_eq!(
+            decode_base58("4fE3H2E6XMp4SsxtwinF7w9a34ooUrwWe4WsW1458Pd").unwrap(),
+            hex::decode("eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c").unwrap()
+        );
+        assert_eq!(
+            decode_base58("4fE3H2E6XMp4SsxtwinF7w9a34ooUrwWe4WsW1458P0"),
+            Err(DecodeError::InvalidCharacter('0'))
+        );
+    }
+
+    #[test]
+    fn test_decode_base58_checksum() {
+        let payload = hex::decode("6f4b3518229b0d3554fe7cd3796ade632aff3069d8").unwrap();
+        let encoded = encode_base58_checksum(&mut payload.clone());
+        assert_eq!(encoded, "mnNcaVkC35ezZSgvn8fhXEa9QTHSUtPfzQ");
+        assert_eq!(decode_base58_checksum(&encoded).unwrap(), payload);
+
+        assert_eq!(
+            decode_base58_checksum("mnNcaVkC35ezZSgvn8fhXEa9QTHSUtPfzR"),
+            Err(DecodeError::InvalidChecksum)
+        );
+        assert_eq!(
+            decode_base58_checksum("2g"),
+            Err(DecodeError::InvalidLength(1))
+        );
+    }
+
     #[test]
     fn test_merkle_parent() {
         let hash0 = hex::decode("c117ea8ec828342f4dfb0ad6bd140e03a50720ece40169ee38bdc15d9eb64cf5")
@@ -0,0 +1,352 @@
+//! Jacobian coordinates for `Point` arithmetic.
+//!
+//! A point (X, Y, Z) stands for the affine point (X/Z², Y/Z³), so additions
+//! and doublings need no field inversion. Only the final conversion back to
+//! affine coordinates inverts Z.
+
+use num_bigint::BigUint;
+use num_traits::{One, ToPrimitive, Zero};
+
+/// Window size of the wNAF multiplication. The table holds
+/// 2^(WINDOW - 2) odd multiples.
+const WINDOW: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Jacobian {
+    x: BigUint,
+    y: BigUint,
+    z: BigUint,
+}
+
+impl Jacobian {
+    pub fn infinity() -> Self {
+        Self {
+            x: BigUint::one(),
+            y: BigUint::one(),
+            z: BigUint::zero(),
+        }
+    }
+
+    pub fn from_affine(x: BigUint, y: BigUint) -> Self {
+        Self {
+            x,
+            y,
+            z: BigUint::one(),
+        }
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    /// Swap `self` and `other` if `flag` is set, with the same operations
+    /// either way.
+    fn cswap(&mut self, other: &mut Self, flag: bool) {
+        let mask = flag as u32;
+        for (a, b) in [
+            (&mut self.x, &mut other.x),
+            (&mut self.y, &mut other.y),
+            (&mut self.z, &mut other.z),
+        ] {
+            let t = (&*a ^ &*b) * mask;
+            *a ^= &t;
+            *b ^= &t;
+        }
+    }
+}
+
+/// A short Weierstrass curve y² = x³ + ax + b over the prime field of `p`.
+/// `b` is not needed by the group law.
+pub(crate) struct Curve {
+    pub a: BigUint,
+    pub p: BigUint,
+}
+
+impl Curve {
+    fn add_mod(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a + b) % &self.p
+    }
+
+    fn sub_mod(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        ((a + &self.p) - b) % &self.p
+    }
+
+    fn mul_mod(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a * b) % &self.p
+    }
+
+    pub fn to_affine(&self, point: &Jacobian) -> Option<(BigUint, BigUint)> {
+        if point.is_infinity() {
+            return None;
+        }
+
+        // 1/z == z^(p-2) by Fermat's little theorem.
+        let zinv = point.z.modpow(&(&self.p - 2u32), &self.p);
+        let zinv2 = self.mul_mod(&zinv, &zinv);
+        let x = self.mul_mod(&point.x, &zinv2);
+        let y = self.mul_mod(&point.y, &self.mul_mod(&zinv2, &zinv));
+        Some((x, y))
+    }
+
+    pub fn neg(&self, point: &Jacobian) -> Jacobian {
+        Jacobian {
+            x: point.x.clone(),
+            y: self.sub_mod(&BigUint::zero(), &point.y),
+            z: point.z.clone(),
+        }
+    }
+
+    pub fn double(&self, point: &Jacobian) -> Jacobian {
+        if point.is_infinity() || point.y.is_zero() {
+            return Jacobian::infinity();
+        }
+
+        // dbl-1998-cmo-2
+        let xx = self.mul_mod(&point.x, &point.x);
+        let yy = self.mul_mod(&point.y, &point.y);
+        let yyyy = self.mul_mod(&yy, &yy);
+        let zz = self.mul_mod(&point.z, &point.z);
+        // s = 4xy²
+        let s = self.mul_mod(&(4u32 * &point.x), &yy);
+        // m = 3x² + az⁴
+        let m = self.add_mod(
+            &(3u32 * xx),
+            &self.mul_mod(&self.a, &self.mul_mod(&zz, &zz)),
+        );
+        // x3 = m² - 2s
+        let x3 = self.sub_mod(&self.mul_mod(&m, &m), &((2u32 * &s) % &self.p));
+        // y3 = m(s - x3) - 8y⁴
+        let y3 = self.sub_mod(
+            &self.mul_mod(&m, &self.sub_mod(&s, &x3)),
+            &((8u32 * yyyy) % &self.p),
+        );
+        // z3 = 2yz
+        let z3 = self.mul_mod(&(2u32 * &point.y), &point.z);
+
+        Jacobian {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    pub fn add(&self, p1: &Jacobian, p2: &Jacobian) -> Jacobian {
+        if p1.is_infinity() {
+            return p2.clone();
+        }
+        if p2.is_infinity() {
+            return p1.clone();
+        }
+
+        // add-1998-cmo-2
+        let z1z1 = self.mul_mod(&p1.z, &p1.z);
+        let z2z2 = self.mul_mod(&p2.z, &p2.z);
+        let u1 = self.mul_mod(&p1.x, &z2z2);
+        let u2 = self.mul_mod(&p2.x, &z1z1);
+        let s1 = self.mul_mod(&p1.y, &self.mul_mod(&p2.z, &z2z2));
+        let s2 = self.mul_mod(&p2.y, &self.mul_mod(&p1.z, &z1z1));
+
+        if u1 == u2 {
+            // Same x: either the same point or its negation.
+            if s1 == s2 {
+                return self.double(p1);
+            }
+            return Jacobian::infinity();
+        }
+
+        let h = self.sub_mod(&u2, &u1);
+        let r = self.sub_mod(&s2, &s1);
+        let hh = self.mul_mod(&h, &h);
+        let hhh = self.mul_mod(&h, &hh);
+        let v = self.mul_mod(&u1, &hh);
+        // x3 = r² - h³ - 2v
+        let x3 = self.sub_mod(
+            &self.sub_mod(&self.mul_mod(&r, &r), &hhh),
+            &((2u32 * &v) % &self.p),
+        );
+        // y3 = r(v - x3) - s1h³
+        let y3 = self.sub_mod(
+            &self.mul_mod(&r, &self.sub_mod(&v, &x3)),
+            &self.mul_mod(&s1, &hhh),
+        );
+        // z3 = z1z2h
+        let z3 = self.mul_mod(&self.mul_mod(&p1.z, &p2.z), &h);
+
+        Jacobian {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// `k * point` with a width-`WINDOW` non-adjacent form. Runs in
+    /// variable time.
+    pub fn mul_wnaf(&self, point: &Jacobian, k: &BigUint) -> Jacobian {
+        // Odd multiples P, 3P, 5P, ...
+        let double = self.double(point);
+        let mut table = vec![point.clone()];
+        for i in 1..1 << (WINDOW - 2) {
+            table.push(self.add(&table[i - 1], &double));
+        }
+
+        let mut result = Jacobian::infinity();
+        for &digit in wnaf(k, WINDOW).iter().rev() {
+            result = self.double(&result);
+            if digit > 0 {
+                result = self.add(&result, &table[(digit / 2) as usize]);
+            } else if digit < 0 {
+                result = self.add(&result, &self.neg(&table[(-digit / 2) as usize]));
+            }
+        }
+        result
+    }
+
+    /// `k * point` with a Montgomery ladder over `bits` bits, doing one
+    /// addition and one doubling per bit whatever the scalar.
+    ///
+    /// The ladder runs on `k + 2^bits`, whose top bit is always set, so it
+    /// starts from `(point, 2 * point)` rather than the point at infinity,
+    /// and `2^bits * point`, which does not depend on `k`, is subtracted at
+    /// the end. The bits of `k` only select a conditional swap.
+    ///
+    /// This removes the branches on the scalar from the group operations,
+    /// but `BigUint` arithmetic is itself not constant time, and neither are
+    /// the exceptional cases of `add` and `double`, which an intermediate
+    /// multiple of the point's order reaches. It is not constant time.
+    pub fn mul_ladder(&self, point: &Jacobian, k: &BigUint, bits: u64) -> Jacobian {
+        let bits = bits.max(k.bits());
+
+        let mut r0 = point.clone();
+        let mut r1 = self.double(point);
+        let mut swap = false;
+        for i in (0..bits).rev() {
+            let bit = k.bit(i);
+            r0.cswap(&mut r1, swap ^ bit);
+            swap = bit;
+            r1 = self.add(&r0, &r1);
+            r0 = self.double(&r0);
+        }
+        r0.cswap(&mut r1, swap);
+
+        let mut offset = point.clone();
+        for _ in 0..bits {
+            offset = self.double(&offset);
+        }
+        self.add(&r0, &self.neg(&offset))
+    }
+}
+
+/// Signed digits of `k`, least significant first. Non-zero digits are odd,
+/// below 2^(w-1) in absolute value, and followed by at least w - 1 zeros.
+fn wnaf(k: &BigUint, w: usize) -> Vec<i32> {
+    let mut k = k.clone();
+    let window = 1i32 << w;
+    let mut digits = Vec::with_capacity(k.bits() as usize + 1);
+
+    while !k.is_zero() {
+        let mut digit = 0;
+        if k.bit(0) {
+            digit = (&k % window as u32).to_i32().unwrap();
+            if digit >= window / 2 {
+                digit -= window;
+            }
+            if digit > 0 {
+                k -= digit as u32;
+            } else {
+                k += (-digit) as u32;
+            }
+        }
+        digits.push(digit);
+        k >>= 1;
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigUint, RandBigInt};
+    use num_traits::{Num, One, Zero};
+    use rand::thread_rng;
+
+    use super::{wnaf, Curve, Jacobian, WINDOW};
+    use crate::{Point, N};
+
+    #[test]
+    fn test_wnaf() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let k = rng.gen_biguint(256);
+            let digits = wnaf(&k, WINDOW);
+
+            let mut sum = num_bigint::BigInt::zero();
+            for (i, &d) in digits.iter().enumerate() {
+                sum += num_bigint::BigInt::from(d) << i;
+                if d != 0 {
+                    assert!(d % 2 != 0 && d.abs() < 1 << (WINDOW - 1));
+                    for &next in digits.iter().skip(i + 1).take(WINDOW - 1) {
+                        assert_eq!(next, 0);
+                    }
+                }
+            }
+            assert_eq!(sum, k.into());
+        }
+    }
+
+    #[test]
+    fn test_small_curve() {
+        // y² = x³ + 7 over F_223 has order 21 at (47, 71); (15, 86) has
+        // order 7.
+        let curve = Curve {
+            a: BigUint::zero(),
+            p: BigUint::from(223u32),
+        };
+        for (x, y, order) in [(47u32, 71u32, 21u32), (15, 86, 7)] {
+            let point = Jacobian::from_affine(x.into(), y.into());
+
+            // Repeated addition as the reference.
+            let mut expected = Jacobian::infinity();
+            for k in 0..=order {
+                let k = BigUint::from(k);
+                let wnaf = curve.mul_wnaf(&point, &k);
+                let ladder = curve.mul_ladder(&point, &k, 8);
+                assert_eq!(curve.to_affine(&wnaf), curve.to_affine(&expected));
+                assert_eq!(curve.to_affine(&ladder), curve.to_affine(&expected));
+                expected = curve.add(&expected, &point);
+            }
+            assert!(curve.mul_wnaf(&point, &order.into()).is_infinity());
+        }
+    }
+
+    #[test]
+    fn test_cswap() {
+        let a = Jacobian::from_affine(47u32.into(), 71u32.into());
+        let b = Jacobian::infinity();
+
+        let (mut x, mut y) = (a.clone(), b.clone());
+        x.cswap(&mut y, false);
+        assert_eq!((&x, &y), (&a, &b));
+        x.cswap(&mut y, true);
+        assert_eq!((&x, &y), (&b, &a));
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let g = Point::get_point_g();
+        let n = BigUint::from_str_radix(N, 16).unwrap();
+        let mut rng = thread_rng();
+
+        for _ in 0..10 {
+            let k = rng.gen_biguint_below(&n);
+            let ladder = g.rmul_ladder(k.clone());
+            assert_eq!(g.rmul(k.clone()), ladder);
+
+            // k * G + G == (k + 1) * G, through the affine group law.
+            assert_eq!(ladder + g.clone(), g.rmul(k + BigUint::one()));
+        }
+        assert!(g.rmul_ladder(n.clone()).is_infinity());
+        // (n - 1) * G == -G
+        let minus_g = g.rmul(n - BigUint::one());
+        assert_eq!(minus_g.x, g.x);
+        assert_eq!(minus_g.y.unwrap().num + &g.y.unwrap().num, g.a.prime);
+    }
+}
@@ -1,12 +1,20 @@
-use std::ops::Add;
+use std::{fmt, ops::Add};
 
-use finite_fields::{FieldElement, P};
-use helper::{encode_base58_checksum, hash160};
+use address::Network;
+use bech32::encode_segwit_address;
+use finite_fields::{FieldElement, PrimeField, P};
+use helper::{encode_base58_checksum, hash160, tagged_hash, to_bytes32};
+use jacobian::{Curve, Jacobian};
 use num_bigint::BigUint;
-use num_traits::{FromPrimitive, Num, One, Zero};
+use num_traits::{FromPrimitive, Num, Zero};
 use signature::Signature;
 
+pub mod address;
+pub mod bech32;
+#[cfg(feature = "libsecp256k1")]
+mod bridge;
 pub mod helper;
+mod jacobian;
 pub mod private_key;
 pub mod signature;
 
@@ -14,6 +22,42 @@ const A: &str = "000000000000000000000000000000000000000000000000000000000000000
 const B: &str = "0000000000000000000000000000000000000000000000000000000000000007";
 const N: &str = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141";
 
+/// Errors when constructing or parsing a `Point`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointError {
+    /// The coordinates do not satisfy the curve equation, are not field
+    /// elements, or a compressed x has no matching y.
+    NotOnCurve,
+    /// Unknown SEC prefix byte.
+    InvalidPrefix(u8),
+    /// Wrong SEC encoding length.
+    InvalidLength(usize),
+    /// The point at infinity, which has no affine coordinates.
+    Infinity,
+    /// Recovery id above 3.
+    InvalidRecoveryId(u8),
+    /// Signature values not in the range 1 to n - 1.
+    InvalidSignature,
+    /// Coordinates and curve parameters from different fields.
+    FieldMismatch,
+}
+
+impl fmt::Display for PointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PointError::NotOnCurve => write!(f, "point is not on the curve"),
+            PointError::InvalidPrefix(p) => write!(f, "invalid SEC prefix {:#04x}", p),
+            PointError::InvalidLength(len) => write!(f, "invalid SEC length {}", len),
+            PointError::Infinity => write!(f, "point at infinity"),
+            PointError::InvalidRecoveryId(id) => write!(f, "invalid recovery id {}", id),
+            PointError::InvalidSignature => write!(f, "invalid signature"),
+            PointError::FieldMismatch => write!(f, "elements of different fields"),
+        }
+    }
+}
+
+impl std::error::Error for PointError {}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Point {
     pub x: Option<FieldElement>,
@@ -30,29 +74,49 @@ impl Point {
         a: Option<FieldElement>,
         b: Option<FieldElement>,
     ) -> Self {
-        let a = if a.is_none() {
-            FieldElement::new(BigUint::from_str_radix(A, 16).unwrap(), None)
-        } else {
-            a.unwrap()
-        };
+        match Self::try_new(x.clone(), y.clone(), a, b) {
+            Ok(point) => point,
+            Err(_) => panic!("({:?}, {:?}) is not on the curve", x, y),
+        }
+    }
 
-        let b = if b.is_none() {
-            FieldElement::new(BigUint::from_str_radix(B, 16).unwrap(), None)
-        } else {
-            b.unwrap()
-        };
+    /// Like `new`, but returns `PointError::FieldMismatch` or
+    /// `PointError::NotOnCurve` instead of panicking.
+    pub fn try_new(
+        x: Option<FieldElement>,
+        y: Option<FieldElement>,
+        a: Option<FieldElement>,
+        b: Option<FieldElement>,
+    ) -> Result<Self, PointError> {
+        let a =
+            a.unwrap_or_else(|| FieldElement::new(BigUint::from_str_radix(A, 16).unwrap(), None));
+        let b =
+            b.unwrap_or_else(|| FieldElement::new(BigUint::from_str_radix(B, 16).unwrap(), None));
+
+        // Arithmetic on elements of different fields panics.
+        if [&x, &y, &Some(b.clone())]
+            .into_iter()
+            .flatten()
+            .any(|fe| fe.prime != a.prime)
+        {
+            return Err(PointError::FieldMismatch);
+        }
 
-        if let (Some(x_field), Some(y_field)) = (x.clone(), y.clone()) {
-            if y_field.to_the_power_of(BigUint::from_u8(2).unwrap())
-                != x_field.to_the_power_of(BigUint::from_u8(3).unwrap())
-                    + (a.clone() * x_field.clone())
-                    + b.clone()
-            {
-                panic!("({:?}, {:?}) is not on the curve", x_field, y_field);
+        match (x.clone(), y.clone()) {
+            (Some(x_field), Some(y_field)) => {
+                if y_field.to_the_power_of(BigUint::from_u8(2).unwrap())
+                    != x_field.to_the_power_of(BigUint::from_u8(3).unwrap())
+                        + (a.clone() * x_field.clone())
+                        + b.clone()
+                {
+                    return Err(PointError::NotOnCurve);
+                }
             }
+            (None, None) => (),
+            _ => return Err(PointError::NotOnCurve),
         }
 
-        Self { a, b, x, y }
+        Ok(Self { a, b, x, y })
     }
 
     pub fn equal(&self, other: Option<Point>) -> bool {
@@ -63,18 +127,57 @@ impl Point {
         *self != other.unwrap()
     }
 
+    /// Scalar multiplication, computed in Jacobian coordinates with a wNAF.
+    /// The group operations depend on the scalar; see `rmul_ladder`.
     pub fn rmul(&self, coefficient: BigUint) -> Self {
-        let mut coef = coefficient;
-        let mut current = self.clone();
-        let mut result = Self::new(None, None, Some(self.a.clone()), Some(self.b.clone()));
-        while coef.clone() != BigUint::zero() {
-            if coef.clone() % (BigUint::one() + BigUint::one()) == BigUint::one() {
-                result = result + current.clone();
-            }
-            current = current.clone() + current.clone();
-            coef >>= 1;
+        let curve = self.curve();
+        let result = curve.mul_wnaf(&self.to_jacobian(), &coefficient);
+        self.with_jacobian(&curve, &result)
+    }
+
+    /// Scalar multiplication with a Montgomery ladder, which performs the
+    /// same sequence of group operations for every scalar below the field
+    /// size.
+    ///
+    /// This is not constant time. Only the sequence of group operations is
+    /// uniform: the `BigUint` arithmetic inside them still takes time that
+    /// depends on the values, and so does the rare exceptional case of an
+    /// intermediate result at infinity.
+    pub fn rmul_ladder(&self, coefficient: BigUint) -> Self {
+        let curve = self.curve();
+        let bits = curve.p.bits();
+        let result = curve.mul_ladder(&self.to_jacobian(), &coefficient, bits);
+        self.with_jacobian(&curve, &result)
+    }
+
+    fn curve(&self) -> Curve {
+        Curve {
+            a: self.a.num.clone(),
+            p: self.a.prime.clone(),
+        }
+    }
+
+    fn to_jacobian(&self) -> Jacobian {
+        match (&self.x, &self.y) {
+            (Some(x), Some(y)) => Jacobian::from_affine(x.num.clone(), y.num.clone()),
+            _ => Jacobian::infinity(),
+        }
+    }
+
+    fn with_jacobian(&self, curve: &Curve, point: &Jacobian) -> Self {
+        let (x, y) = match curve.to_affine(point) {
+            Some((x, y)) => (
+                Some(FieldElement::new(x, Some(curve.p.clone()))),
+                Some(FieldElement::new(y, Some(curve.p.clone()))),
+            ),
+            None => (None, None),
+        };
+        Self {
+            x,
+            y,
+            a: self.a.clone(),
+            b: self.b.clone(),
         }
-        result
     }
 
     pub fn is_infinity(&self) -> bool {
@@ -108,104 +211,140 @@ impl Point {
         }
     }
 
+    #[cfg(feature = "fast")]
+    pub fn verify(&self, z: &BigUint, sig: &Signature) -> bool {
+        bridge::verify(self, z, sig)
+    }
+
+    #[cfg(not(feature = "fast"))]
     pub fn verify(&self, z: &BigUint, sig: &Signature) -> bool {
         let n = BigUint::from_str_radix(N, 16).unwrap();
         let g = Point::get_point_g();
 
-        let s_inv =
-            FieldElement::mod_pow(&sig.s, n.clone() - (BigUint::one() + BigUint::one()), &n);
+        if self.is_infinity() || sig.r.is_zero() || sig.r >= n || sig.s.is_zero() || sig.s >= n {
+            return false;
+        }
+
+        let s_inv = FieldElement::mod_pow(&sig.s, &n - 2u32, &n);
         let u = z * s_inv.clone() % n.clone();
         let v = sig.r.clone() * s_inv.clone() % n.clone();
 
         let total = g.rmul(u) + self.rmul(v);
 
-        total.x.unwrap().num == sig.r
+        match total.x {
+            Some(x) => x.num % n == sig.r,
+            None => false,
+        }
     }
 
+    /// Recover the public key that signed `z`, from the signature and the
+    /// recovery id returned by `PrivateKey::sign_recoverable`.
+    pub fn recover(z: &BigUint, sig: &Signature, recid: u8) -> Result<Self, PointError> {
+        let n = BigUint::from_str_radix(N, 16).unwrap();
+        if recid > 3 {
+            return Err(PointError::InvalidRecoveryId(recid));
+        }
+        if sig.r.is_zero() || sig.r >= n || sig.s.is_zero() || sig.s >= n {
+            return Err(PointError::InvalidSignature);
+        }
+
+        // R.x is r, or r + n if it overflowed, and R.y has the parity of
+        // bit 0.
+        let mut x = sig.r.clone();
+        if recid & 2 != 0 {
+            x += &n;
+        }
+        if x.bits() > 256 {
+            return Err(PointError::NotOnCurve);
+        }
+        let mut sec = vec![0x02 | (recid & 1)];
+        sec.extend(to_bytes32(&x));
+        let big_r = Self::try_parse(&sec)?;
+
+        // Q = r^-1 (sR - zG)
+        let r_inv = FieldElement::mod_pow(&sig.r, &n - 2u32, &n);
+        let u1 = (&n - z % &n) * &r_inv % &n;
+        let u2 = &sig.s * &r_inv % &n;
+        let q = Self::get_point_g().rmul(u1) + big_r.rmul(u2);
+        if q.is_infinity() {
+            return Err(PointError::Infinity);
+        }
+        Ok(q)
+    }
+
+    /// SEC encoding of the point. The point at infinity encodes as a single
+    /// zero byte.
     pub fn sec(&self, compressed: bool) -> Vec<u8> {
-        let prefix_bytes: Vec<u8>;
-        let x_bytes: Vec<u8>;
-        let mut result = Vec::new();
-        let concated_res: Vec<u8>;
+        let (x, y) = match (&self.x, &self.y) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return vec![0x00],
+        };
 
+        let mut result = Vec::new();
         if compressed {
-            if self.y.clone().unwrap().num % (BigUint::one() + BigUint::one()) == BigUint::zero() {
-                prefix_bytes = b"\x02".to_vec();
-                x_bytes = self.x.clone().unwrap().num.to_bytes_be();
+            if y.num.bit(0) {
+                result.push(0x03);
             } else {
-                prefix_bytes = b"\x03".to_vec();
-                x_bytes = self.x.clone().unwrap().num.to_bytes_be();
+                result.push(0x02);
             }
-            result.push(prefix_bytes);
-            result.push(x_bytes);
-
-            concated_res = result.concat();
+            result.extend(to_bytes32(&x.num));
         } else {
-            prefix_bytes = b"\x04".to_vec();
-            x_bytes = self.x.clone().unwrap().num.to_bytes_be();
-            let y_bytes = self.y.clone().unwrap().num.to_bytes_be();
-
-            result.push(prefix_bytes);
-            result.push(x_bytes);
-            result.push(y_bytes);
-
-            concated_res = result.concat();
+            result.push(0x04);
+            result.extend(to_bytes32(&x.num));
+            result.extend(to_bytes32(&y.num));
         }
 
-        concated_res
+        result
     }
 
     // when we get a serialized SEC pubkey, we can write a parse method to figure out which y we
     // need:
     pub fn parse(sec_bin: Vec<u8>) -> Self {
-        if let Some(zero) = sec_bin.get(0) {
-            if zero == &4 {
-                let (left, right) = sec_bin.split_at(33);
-                let x = BigUint::from_bytes_be(&left[1..]);
-                let y = BigUint::from_bytes_be(&right);
-                return Self::new(
-                    Some(FieldElement::new(x, None)),
-                    Some(FieldElement::new(y, None)),
-                    None,
-                    None,
-                );
-            }
-        }
+        Self::try_parse(&sec_bin).unwrap()
+    }
 
-        let is_even = if sec_bin.get(0) == Some(&2) {
-            true
-        } else {
-            false
+    /// Parse a compressed or uncompressed SEC public key on secp256k1.
+    pub fn try_parse(sec_bin: &[u8]) -> Result<Self, PointError> {
+        let p = BigUint::from_str_radix(P, 16).unwrap();
+        let field_element = |bytes: &[u8]| {
+            let num = BigUint::from_bytes_be(bytes);
+            if num >= p {
+                return Err(PointError::NotOnCurve);
+            }
+            Ok(FieldElement::new(num, None))
         };
 
-        let x = FieldElement::new(BigUint::from_bytes_be(&sec_bin[1..]), None);
-
-        // right side of the equation y ^ 2 = x^ 3 + 7
-        let alpha = x.to_the_power_of(BigUint::from_u8(3).unwrap())
-            + FieldElement::new(BigUint::from_u8(7).unwrap(), None);
-        // solve for left side
-        let beta = alpha.sqrt();
-
-        let even_beta: FieldElement;
-        let odd_beta: FieldElement;
-        if beta.clone().num % (BigUint::one() + BigUint::one()) == BigUint::zero() {
-            even_beta = beta.clone();
-            odd_beta = FieldElement::new(
-                BigUint::from_str_radix(P, 16).unwrap() - beta.clone().num,
-                None,
-            );
-        } else {
-            even_beta = FieldElement::new(
-                BigUint::from_str_radix(P, 16).unwrap() - beta.clone().num,
-                None,
-            );
-            odd_beta = beta.clone();
-        }
-
-        if is_even {
-            Self::new(Some(x), Some(even_beta), None, None)
-        } else {
-            Self::new(Some(x), Some(odd_beta), None, None)
+        match sec_bin.first() {
+            None => Err(PointError::InvalidLength(0)),
+            Some(0x00) if sec_bin.len() == 1 => Err(PointError::Infinity),
+            Some(0x04) => {
+                if sec_bin.len() != 65 {
+                    return Err(PointError::InvalidLength(sec_bin.len()));
+                }
+                let x = field_element(&sec_bin[1..33])?;
+                let y = field_element(&sec_bin[33..])?;
+                Self::try_new(Some(x), Some(y), None, None)
+            }
+            Some(&prefix @ (0x02 | 0x03)) => {
+                if sec_bin.len() != 33 {
+                    return Err(PointError::InvalidLength(sec_bin.len()));
+                }
+                let x = field_element(&sec_bin[1..])?;
+
+                // right side of the equation y ^ 2 = x^ 3 + 7
+                let alpha = x.to_the_power_of(BigUint::from_u8(3).unwrap())
+                    + FieldElement::new(BigUint::from_u8(7).unwrap(), None);
+                // solve for left side
+                let beta = alpha.sqrt().ok_or(PointError::NotOnCurve)?;
+
+                let y = if beta.num.bit(0) == (prefix == 0x03) {
+                    beta
+                } else {
+                    FieldElement::new((&p - &beta.num) % &p, None)
+                };
+                Self::try_new(Some(x), Some(y), None, None)
+            }
+            Some(&prefix) => Err(PointError::InvalidPrefix(prefix)),
         }
     }
 
@@ -228,6 +367,46 @@ impl Point {
 
         encode_base58_checksum(&mut b)
     }
+
+    /// Native SegWit v0 address of the compressed public key.
+    pub fn p2wpkh_address(&self, network: Network) -> String {
+        encode_segwit_address(network.hrp(), 0, &self.hash160(true))
+    }
+
+    /// P2WPKH nested in P2SH, for wallets without bech32 support.
+    pub fn p2sh_p2wpkh_address(&self, network: Network) -> String {
+        let mut redeem_script = vec![0x00, 0x14];
+        redeem_script.extend(self.hash160(true));
+
+        let mut b = vec![network.p2sh_prefix()];
+        b.extend(hash160(&redeem_script));
+        encode_base58_checksum(&mut b)
+    }
+
+    /// Key-path only taproot address (BIP86), using this point as the
+    /// internal key.
+    ///
+    /// Fails at infinity, which has no x-only key.
+    pub fn p2tr_address(&self, network: Network) -> Result<String, PointError> {
+        Ok(encode_segwit_address(
+            network.hrp(),
+            1,
+            &self.taproot_output_key()?,
+        ))
+    }
+
+    /// x-only output key of BIP341, tweaked with an empty script tree.
+    fn taproot_output_key(&self) -> Result<[u8; 32], PointError> {
+        // The internal key is the point with the same x and an even y.
+        let x = to_bytes32(&self.x.as_ref().ok_or(PointError::Infinity)?.num);
+        let mut sec = vec![0x02];
+        sec.extend_from_slice(&x);
+        let internal = Self::parse(sec);
+
+        let tweak = BigUint::from_bytes_be(&tagged_hash("TapTweak", &x));
+        let output = internal + Self::get_point_g().rmul(tweak);
+        Ok(to_bytes32(&output.x.ok_or(PointError::Infinity)?.num))
+    }
 }
 
 impl Add for Point {
@@ -325,9 +504,12 @@ impl Add for Point {
 mod tests {
     use finite_fields::FieldElement;
     use num_bigint::BigUint;
-    use num_traits::{FromPrimitive, Num, One};
+    use num_traits::{FromPrimitive, Num, One, Zero};
 
-    use crate::{helper::hash256, private_key::PrivateKey, signature::Signature, Point, N};
+    use crate::{
+        address::Network, helper::hash256, private_key::PrivateKey, signature::Signature, Point,
+        PointError, N,
+    };
 
     #[test]
     fn test_secp256k1() {
@@ -504,4 +686,185 @@ c13b4a4994f17691895806e1b40b57f4fd22581a4f46851f3b06"
 
         println!("{:?}", address);
     }
+
+    #[test]
+    fn test_segwit_address() {
+        let g = Point::get_point_g();
+        assert_eq!(
+            g.p2wpkh_address(Network::Mainnet),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        assert_eq!(
+            g.p2wpkh_address(Network::Testnet),
+            "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+        );
+        assert_eq!(
+            g.p2wpkh_address(Network::Signet),
+            g.p2wpkh_address(Network::Testnet)
+        );
+        assert_eq!(
+            g.p2wpkh_address(Network::Regtest),
+            "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080"
+        );
+
+        assert_eq!(
+            g.p2sh_p2wpkh_address(Network::Mainnet),
+            "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN"
+        );
+        assert_eq!(
+            g.p2sh_p2wpkh_address(Network::Regtest),
+            "2NAUYAHhujozruyzpsFRP63mbrdaU5wnEpN"
+        );
+    }
+
+    #[test]
+    fn test_p2tr_address() {
+        let g = Point::get_point_g();
+        assert_eq!(
+            g.p2tr_address(Network::Mainnet).unwrap(),
+            "bc1pmfr3p9j00pfxjh0zmgp99y8zftmd3s5pmedqhyptwy6lm87hf5sspknck9"
+        );
+        assert_eq!(
+            g.p2tr_address(Network::Signet).unwrap(),
+            "tb1pmfr3p9j00pfxjh0zmgp99y8zftmd3s5pmedqhyptwy6lm87hf5ssk79hv2"
+        );
+        assert_eq!(
+            g.p2tr_address(Network::Regtest).unwrap(),
+            "bcrt1pmfr3p9j00pfxjh0zmgp99y8zftmd3s5pmedqhyptwy6lm87hf5ssm803es"
+        );
+
+        // BIP86, first receiving address of the test mnemonic.
+        let mut sec = vec![0x02];
+        sec.extend(
+            hex::decode("cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115")
+                .unwrap(),
+        );
+        let internal = Point::parse(sec);
+        assert_eq!(
+            internal.p2tr_address(Network::Mainnet).unwrap(),
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        );
+        // Only the x coordinate of the internal key matters.
+        let negated = internal.rmul(BigUint::from_str_radix(N, 16).unwrap() - BigUint::one());
+        assert_eq!(
+            negated.p2tr_address(Network::Mainnet).unwrap(),
+            internal.p2tr_address(Network::Mainnet).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_try_new() {
+        let prime = BigUint::from_u8(223).unwrap();
+        let fe = |n: u8| {
+            Some(FieldElement::new(
+                BigUint::from_u8(n).unwrap(),
+                Some(prime.clone()),
+            ))
+        };
+
+        assert!(Point::try_new(fe(47), fe(71), fe(0), fe(7)).is_ok());
+        assert!(Point::try_new(None, None, fe(0), fe(7))
+            .unwrap()
+            .is_infinity());
+        assert_eq!(
+            Point::try_new(fe(47), fe(72), fe(0), fe(7)),
+            Err(PointError::NotOnCurve)
+        );
+        assert_eq!(
+            Point::try_new(fe(47), None, fe(0), fe(7)),
+            Err(PointError::NotOnCurve)
+        );
+
+        let other = |n: u8| {
+            Some(FieldElement::new(
+                BigUint::from_u8(n).unwrap(),
+                Some(BigUint::from_u8(227).unwrap()),
+            ))
+        };
+        for (x, y, a, b) in [
+            (other(47), fe(71), fe(0), fe(7)),
+            (fe(47), other(71), fe(0), fe(7)),
+            (fe(47), fe(71), other(0), fe(7)),
+            (fe(47), fe(71), fe(0), other(7)),
+            (None, None, fe(0), other(7)),
+        ] {
+            assert_eq!(Point::try_new(x, y, a, b), Err(PointError::FieldMismatch));
+        }
+    }
+
+    #[test]
+    fn test_try_parse() {
+        let g = Point::get_point_g();
+        for compressed in [true, false] {
+            assert_eq!(Point::try_parse(&g.sec(compressed)), Ok(g.clone()));
+        }
+
+        // x has a leading zero byte, which must still take 32 bytes.
+        let point = g.rmul(BigUint::from_u8(153).unwrap());
+        for compressed in [true, false] {
+            let sec = point.sec(compressed);
+            assert_eq!(sec.len(), if compressed { 33 } else { 65 });
+            assert_eq!(sec[1], 0);
+            assert_eq!(Point::try_parse(&sec), Ok(point.clone()));
+        }
+
+        let sec = g.sec(false);
+        assert_eq!(Point::try_parse(&[]), Err(PointError::InvalidLength(0)));
+        assert_eq!(
+            Point::try_parse(&sec[..64]),
+            Err(PointError::InvalidLength(64))
+        );
+        assert_eq!(
+            Point::try_parse(&g.sec(true)[..32]),
+            Err(PointError::InvalidLength(32))
+        );
+        let mut hybrid = sec.clone();
+        hybrid[0] = 0x06;
+        assert_eq!(
+            Point::try_parse(&hybrid),
+            Err(PointError::InvalidPrefix(0x06))
+        );
+        let mut off_curve = sec.clone();
+        off_curve[64] ^= 1;
+        assert_eq!(Point::try_parse(&off_curve), Err(PointError::NotOnCurve));
+        // x = 5 has no y on secp256k1.
+        let mut no_y = vec![0x02];
+        no_y.extend([0u8; 31]);
+        no_y.push(5);
+        assert_eq!(Point::try_parse(&no_y), Err(PointError::NotOnCurve));
+        // x >= p.
+        let mut too_large = vec![0x02];
+        too_large.extend([0xff; 32]);
+        assert_eq!(Point::try_parse(&too_large), Err(PointError::NotOnCurve));
+        assert_eq!(Point::try_parse(&[0x00]), Err(PointError::Infinity));
+    }
+
+    #[test]
+    fn test_infinity_handling() {
+        let n = BigUint::from_str_radix(N, 16).unwrap();
+        let infinity = Point::get_point_g().rmul(n.clone());
+
+        assert_eq!(infinity.sec(true), vec![0x00]);
+        assert_eq!(infinity.sec(false), vec![0x00]);
+        assert_eq!(
+            Point::try_parse(&infinity.sec(true)),
+            Err(PointError::Infinity)
+        );
+        // No panics, even though the address cannot be spent from.
+        infinity.address(true, false);
+        infinity.p2wpkh_address(Network::Mainnet);
+        assert_eq!(
+            infinity.p2tr_address(Network::Mainnet),
+            Err(PointError::Infinity)
+        );
+
+        let z = BigUint::from_bytes_be(&hash256(b"message"));
+        let sig = PrivateKey::new(BigUint::from_u8(1).unwrap()).sign(z.clone());
+        assert!(!infinity.verify(&z, &sig));
+
+        let g = Point::get_point_g();
+        assert!(g.verify(&z, &sig));
+        assert!(!g.verify(&z, &Signature::new(BigUint::zero(), sig.s.clone())));
+        assert!(!g.verify(&z, &Signature::new(sig.r.clone(), n)));
+    }
 }
@@ -1,9 +1,16 @@
 use finite_fields::FieldElement;
-use num_bigint::BigUint;
-use num_traits::{Num, One};
+use hmac::{Hmac, Mac};
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{Num, One, Zero};
 use rand::Rng;
+use sha2::Sha256;
 
-use crate::{helper::encode_base58_checksum, signature::Signature, Point, N};
+use crate::{
+    address::Network,
+    helper::{decode_base58_checksum, encode_base58_checksum, to_bytes32, DecodeError},
+    signature::Signature,
+    Point, N,
+};
 
 #[derive(Debug)]
 pub struct PrivateKey {
@@ -16,35 +23,86 @@ impl PrivateKey {
         let g = Point::get_point_g();
         Self {
             secret: secret.clone(),
-            point: g.rmul(secret),
+            point: g.rmul_ladder(secret),
         }
     }
 
+    /// Sign `z` with a deterministic nonce (RFC6979).
     pub fn sign(&self, z: BigUint) -> Signature {
-        let n = BigUint::from_str_radix(N, 16).unwrap();
-        let mut rng = rand::thread_rng();
-        let mut k;
+        self.sign_recoverable(z).0
+    }
 
-        loop {
-            k = BigUint::from(rng.gen::<u64>());
+    /// Like `sign`, also returning the recovery id for `Point::recover`.
+    #[cfg(not(feature = "fast"))]
+    pub fn sign_recoverable(&self, z: BigUint) -> (Signature, u8) {
+        let k = self.deterministic_k(&z);
+        self.sign_with_k(z, k)
+    }
 
-            if &k < &n {
-                break;
-            }
-        }
+    /// Like `sign`, also returning the recovery id for `Point::recover`.
+    /// Signs with libsecp256k1, except for secrets it rejects, which sign
+    /// as without the `fast` feature.
+    #[cfg(feature = "fast")]
+    pub fn sign_recoverable(&self, z: BigUint) -> (Signature, u8) {
+        crate::bridge::sign(self, &z).unwrap_or_else(|| {
+            let k = self.deterministic_k(&z);
+            self.sign_with_k(z, k)
+        })
+    }
+
+    /// Sign `z` with a nonce drawn from `rng`.
+    pub fn sign_with_rng<R: Rng>(&self, z: BigUint, rng: &mut R) -> Signature {
+        let n = BigUint::from_str_radix(N, 16).unwrap();
+        let k = rng.gen_biguint_range(&BigUint::one(), &n);
+        self.sign_with_k(z, k).0
+    }
 
+    pub(crate) fn sign_with_k(&self, z: BigUint, k: BigUint) -> (Signature, u8) {
+        let n = BigUint::from_str_radix(N, 16).unwrap();
         let g = Point::get_point_g();
 
-        let r = g.rmul(k.clone()).x.unwrap().num;
+        let big_r = g.rmul_ladder(k.clone());
+        let x = big_r.x.unwrap().num;
+        let r = &x % &n;
+        // Bit 0 is the parity of R.y, bit 1 whether R.x overflowed n.
+        let mut recid = big_r.y.unwrap().num.bit(0) as u8 | ((x >= n) as u8) << 1;
 
         let k_inv = FieldElement::mod_pow(&k, n.clone() - (BigUint::one() + BigUint::one()), &n);
 
         let mut s = (z.clone() + r.clone() * self.secret.clone()) * k_inv % n.clone();
+        // Negating s signs with -k, whose R has the other y.
         if s > n.clone() / (BigUint::one() + BigUint::one()) {
             s = n - s;
+            recid ^= 1;
         }
 
-        Signature { r, s }
+        (Signature { r, s }, recid)
+    }
+
+    /// Nonce for signing `z`, as specified by RFC6979 with HMAC-SHA256.
+    pub fn deterministic_k(&self, z: &BigUint) -> BigUint {
+        let n = BigUint::from_str_radix(N, 16).unwrap();
+        let z = to_bytes32(&(z % &n));
+        // int2octets(x mod n): `to_bytes32` zeroes values over 256 bits.
+        let secret = to_bytes32(&(&self.secret % &n));
+
+        let mut k = [0u8; 32];
+        let mut v = [1u8; 32];
+
+        k = hmac_sha256(&k, &[&v, &[0x00], &secret, &z]);
+        v = hmac_sha256(&k, &[&v]);
+        k = hmac_sha256(&k, &[&v, &[0x01], &secret, &z]);
+        v = hmac_sha256(&k, &[&v]);
+
+        loop {
+            v = hmac_sha256(&k, &[&v]);
+            let candidate = BigUint::from_bytes_be(&v);
+            if candidate >= BigUint::one() && candidate < n {
+                return candidate;
+            }
+            k = hmac_sha256(&k, &[&v, &[0x00]]);
+            v = hmac_sha256(&k, &[&v]);
+        }
     }
 
     pub fn wif(&self, compressed: bool, testnet: bool) -> String {
@@ -56,14 +114,7 @@ impl PrivateKey {
             data.push(0x80);
         }
 
-        let secret_bytes = self.secret.to_bytes_be();
-        let mut res = [0u8; 32];
-        if secret_bytes.len() <= 32 {
-            let offset = 32 - secret_bytes.len();
-            res[offset..].copy_from_slice(&secret_bytes);
-        }
-        data.extend(res);
-
+        data.extend(to_bytes32(&self.secret));
 
         if compressed {
             data.push(0x01);
@@ -71,14 +122,183 @@ impl PrivateKey {
 
         encode_base58_checksum(&mut data)
     }
+
+    /// Parse a WIF, returning the key, whether its public key is compressed
+    /// and its network.
+    pub fn from_wif(wif: &str) -> Result<(Self, bool, Network), DecodeError> {
+        let data = decode_base58_checksum(wif)?;
+
+        let compressed = match data.len() {
+            33 => false,
+            34 if data[33] == 0x01 => true,
+            len => return Err(DecodeError::InvalidLength(len)),
+        };
+        let network = match data[0] {
+            0x80 => Network::Mainnet,
+            0xef => Network::Testnet,
+            version => return Err(DecodeError::InvalidVersion(version)),
+        };
+
+        let secret = BigUint::from_bytes_be(&data[1..33]);
+        let n = BigUint::from_str_radix(N, 16).unwrap();
+        if secret.is_zero() || secret >= n {
+            return Err(DecodeError::InvalidSecret);
+        }
+
+        Ok((Self::new(secret), compressed, network))
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[&[u8]]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+    for d in data {
+        mac.update(d);
+    }
+    mac.finalize().into_bytes().into()
 }
 
 #[cfg(test)]
 mod tests {
     use num_bigint::BigUint;
-    use num_traits::{FromPrimitive, Num};
+    use num_traits::{FromPrimitive, Num, Zero};
+    use sha2::{Digest, Sha256};
 
     use super::PrivateKey;
+    use crate::{address::Network, helper::DecodeError, N};
+
+    fn hex_int(hex: &str) -> BigUint {
+        BigUint::from_str_radix(hex, 16).unwrap()
+    }
+
+    #[test]
+    fn test_from_wif() {
+        let (priv_key, compressed, network) =
+            PrivateKey::from_wif("cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN8rFTv2sfUK").unwrap();
+        assert_eq!(priv_key.secret, BigUint::from_u16(5003).unwrap());
+        assert!(compressed);
+        assert_eq!(network, Network::Testnet);
+
+        let (priv_key, compressed, network) =
+            PrivateKey::from_wif("91avARGdfge8E4tZfYLoxeJ5sGBdNJQH4kvjpWAxgzczjbCwxic").unwrap();
+        assert_eq!(priv_key.secret, BigUint::from_u64(2021_u64.pow(5)).unwrap());
+        assert!(!compressed);
+        assert_eq!(network, Network::Testnet);
+
+        let secret = hex_int("54321deadbeef");
+        for (compressed, testnet) in [(true, false), (false, false)] {
+            let wif = PrivateKey::new(secret.clone()).wif(compressed, testnet);
+            let parsed = PrivateKey::from_wif(&wif).unwrap();
+            assert_eq!(parsed.0.secret, secret);
+            assert_eq!(parsed.1, compressed);
+            assert_eq!(parsed.2, Network::Mainnet);
+        }
+
+        assert_eq!(
+            PrivateKey::from_wif("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgiuQJv1h8Ytr2S53b")
+                .unwrap_err(),
+            DecodeError::InvalidChecksum
+        );
+        // A P2PKH address is not a WIF.
+        assert_eq!(
+            PrivateKey::from_wif("1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF1").unwrap_err(),
+            DecodeError::InvalidLength(21)
+        );
+        assert_eq!(
+            PrivateKey::from_wif(&PrivateKey::new(BigUint::zero()).wif(true, false)).unwrap_err(),
+            DecodeError::InvalidSecret
+        );
+    }
+
+    #[test]
+    fn test_rfc6979() {
+        let n = hex_int(N);
+        // (secret, message, k, r, s), with low s.
+        let vectors = [
+            (
+                BigUint::from_u8(1).unwrap(),
+                "Satoshi Nakamoto",
+                "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15",
+                "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8",
+                "2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5",
+            ),
+            (
+                BigUint::from_u8(1).unwrap(),
+                "All those moments will be lost in time, like tears in rain. Time to die...",
+                "38aa22d72376b4dbc472e06c3ba403ee0a394da63fc58d88686c611aba98d6b3",
+                "8600dbd41e348fe5c9465ab92d23e3db8b98b873beecd930736488696438cb6b",
+                "547fe64427496db33bf66019dacbf0039c04199abb0122918601db38a72cfc21",
+            ),
+            (
+                n - 1u8,
+                "Satoshi Nakamoto",
+                "33a19b60e25fb6f4435af53a3d42d493644827367e6453928554f43e49aa6f90",
+                "fd567d121db66e382991534ada77a6bd3106f0a1098c231e47993447cd6af2d0",
+                "6b39cd0eb1bc8603e159ef5c20a5c8ad685a45b06ce9bebed3f153d10d93bed5",
+            ),
+            (
+                hex_int("f8b8af8ce3c7cca5e300d33939540c10d45ce001b8f252bfbc57ba0342904181"),
+                "Alan Turing",
+                "525a82b70e67874398067543fd84c83d30c175fdc45fdeee082fe13b1d7cfdf1",
+                "7063ae83e7f62bbb171798131b4a0564b956930092b33b07b395615d9ec7e15c",
+                "58dfcc1e00a35e1572f366ffe34ba0fc47db1e7189759b9fb233c5b05ab388ea",
+            ),
+            (
+                hex_int("e91671c46231f833a6406ccbea0e3e392c76c167bac1cb013f6f1013980455c2"),
+                "There is a computer disease that anybody who works with computers knows about. \
+                 It's a very serious disease and it interferes completely with the work. The \
+                 trouble with computers is that you 'play' with them!",
+                "1f4b84c23a86a221d233f2521be018d9318639d5b8bbd6374a8a59232d16ad3d",
+                "b552edd27580141f3b2a5463048cb7cd3e047b97c9f98076c32dbdf85a68718b",
+                "279fa72dd19bfae05577e06c7c0c1900c371fcd5893f7e1d56a37d30174671f6",
+            ),
+        ];
+
+        for (secret, message, k, r, s) in vectors {
+            let priv_key = PrivateKey::new(secret);
+            let z = BigUint::from_bytes_be(&Sha256::digest(message.as_bytes()));
+
+            assert_eq!(priv_key.deterministic_k(&z), hex_int(k));
+            let sig = priv_key.sign(z.clone());
+            assert_eq!(sig.r, hex_int(r));
+            assert_eq!(sig.s, hex_int(s));
+            assert!(priv_key.point.verify(&z, &sig));
+        }
+    }
+
+    #[test]
+    fn test_sign_with_rng() {
+        let priv_key = PrivateKey::new(BigUint::from_u16(12345).unwrap());
+        let z = BigUint::from_bytes_be(&Sha256::digest(b"Programming Bitcoin!"));
+
+        let sig = priv_key.sign_with_rng(z.clone(), &mut rand::thread_rng());
+        assert!(priv_key.point.verify(&z, &sig));
+        assert!(sig.s <= hex_int(N) / 2u8);
+    }
+
+    #[test]
+    fn test_sign_out_of_range_secret() {
+        // libsecp256k1 rejects these, so with the `fast` feature they take
+        // the `BigUint` path instead of panicking.
+        let z = BigUint::from_bytes_be(&Sha256::digest(b"Programming Bitcoin!"));
+        for secret in [BigUint::zero(), hex_int(N), hex_int(N) + 12345u16] {
+            let priv_key = PrivateKey::new(secret);
+            let k = priv_key.deterministic_k(&z);
+            assert_eq!(
+                priv_key.sign_recoverable(z.clone()),
+                priv_key.sign_with_k(z.clone(), k)
+            );
+        }
+
+        // The nonce depends on the secret mod n, however large the secret.
+        let priv_key = PrivateKey::new(BigUint::from_u16(12345).unwrap());
+        for extra in [hex_int(N), hex_int(N) << 300] {
+            let secret = &priv_key.secret + extra;
+            assert_eq!(
+                PrivateKey::new(secret).deterministic_k(&z),
+                priv_key.deterministic_k(&z)
+            );
+        }
+    }
 
     #[test]
     fn test_wif() {
@@ -1,5 +1,12 @@
 use num_bigint::BigUint;
+use num_traits::Zero;
 
+use crate::{
+    helper::{to_bytes32, DecodeError},
+    Point, PointError,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Signature {
     pub r: BigUint,
     pub s: BigUint,
@@ -44,14 +51,226 @@ impl Signature {
 
         full_result
     }
+
+    /// Parse a strict DER signature, as required by BIP66.
+    pub fn parse(der: &[u8]) -> Result<Self, DecodeError> {
+        if der.len() < 8 || der.len() > 72 {
+            return Err(DecodeError::InvalidLength(der.len()));
+        }
+        if der[0] != 0x30 || der[1] as usize != der.len() - 2 {
+            return Err(DecodeError::InvalidDer);
+        }
+
+        let (r, rest) = read_integer(&der[2..])?;
+        let (s, rest) = read_integer(rest)?;
+        if !rest.is_empty() {
+            return Err(DecodeError::InvalidDer);
+        }
+
+        Ok(Self::new(r, s))
+    }
+
+    /// Parse a BER-like signature the way OpenSSL used to before BIP66,
+    /// accepting long-form lengths, excess padding, negative integers and
+    /// trailing bytes. Values of more than 32 bytes give a zero signature,
+    /// which never verifies.
+    pub fn parse_lax(der: &[u8]) -> Result<Self, DecodeError> {
+        let mut pos = 0;
+        if der.first() != Some(&0x30) {
+            return Err(DecodeError::InvalidDer);
+        }
+        pos += 1;
+
+        // The sequence length is not checked.
+        let lenbyte = *der.get(pos).ok_or(DecodeError::InvalidDer)?;
+        pos += 1;
+        if lenbyte & 0x80 != 0 {
+            pos += (lenbyte & 0x7f) as usize;
+            if pos > der.len() {
+                return Err(DecodeError::InvalidDer);
+            }
+        }
+
+        let r = read_integer_lax(der, &mut pos)?;
+        let s = read_integer_lax(der, &mut pos)?;
+
+        match (r, s) {
+            (Some(r), Some(s)) => Ok(Self::new(r, s)),
+            _ => Ok(Self::new(BigUint::zero(), BigUint::zero())),
+        }
+    }
+
+    /// Parse a strict DER signature followed by its sighash byte, as pushed
+    /// in a script_sig.
+    pub fn parse_with_sighash(sig: &[u8]) -> Result<(Self, u8), DecodeError> {
+        let (der, sighash) = split_sighash(sig)?;
+        Ok((Self::parse(der)?, sighash))
+    }
+}
+
+/// A signature with the recovery id needed by `Point::recover`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoverableSignature {
+    pub signature: Signature,
+    pub recid: u8,
+    /// Whether the signer used a compressed public key. This is only
+    /// carried by the compact encoding.
+    pub compressed: bool,
+}
+
+impl RecoverableSignature {
+    pub fn new(signature: Signature, recid: u8, compressed: bool) -> Self {
+        Self {
+            signature,
+            recid,
+            compressed,
+        }
+    }
+
+    /// Serialize to the 65-byte Bitcoin compact format, as produced by
+    /// `signmessage`: a header byte of `27 + recid`, plus 4 if the public
+    /// key is compressed, followed by `r` and `s`.
+    pub fn serialize_compact(&self) -> [u8; 65] {
+        let mut result = [0u8; 65];
+        result[0] = 27 + self.recid + if self.compressed { 4 } else { 0 };
+        result[1..33].copy_from_slice(&to_bytes32(&self.signature.r));
+        result[33..].copy_from_slice(&to_bytes32(&self.signature.s));
+        result
+    }
+
+    /// Parse the 65-byte compact format. This is the reverse of
+    /// `serialize_compact`.
+    pub fn parse_compact(compact: &[u8]) -> Result<Self, DecodeError> {
+        if compact.len() != 65 {
+            return Err(DecodeError::InvalidLength(compact.len()));
+        }
+        let header = compact[0];
+        if !(27..27 + 8).contains(&header) {
+            return Err(DecodeError::InvalidVersion(header));
+        }
+
+        let signature = Signature::new(
+            BigUint::from_bytes_be(&compact[1..33]),
+            BigUint::from_bytes_be(&compact[33..]),
+        );
+        Ok(Self::new(
+            signature,
+            (header - 27) & 3,
+            (header - 27) & 4 != 0,
+        ))
+    }
+
+    /// Recover the public key that signed `z`.
+    pub fn recover(&self, z: &BigUint) -> Result<Point, PointError> {
+        Point::recover(z, &self.signature, self.recid)
+    }
+}
+
+/// Split a script signature into its DER encoding and sighash byte.
+pub fn split_sighash(sig: &[u8]) -> Result<(&[u8], u8), DecodeError> {
+    match sig.split_last() {
+        Some((&sighash, der)) => Ok((der, sighash)),
+        None => Err(DecodeError::InvalidLength(0)),
+    }
+}
+
+/// Read a strict DER integer, returning it and the remaining bytes.
+fn read_integer(data: &[u8]) -> Result<(BigUint, &[u8]), DecodeError> {
+    if data.len() < 2 || data[0] != 0x02 {
+        return Err(DecodeError::InvalidDer);
+    }
+    // Only short-form lengths, and at most 32 bytes plus a sign byte.
+    let len = data[1] as usize;
+    if len == 0 || len > 33 || data.len() < 2 + len {
+        return Err(DecodeError::InvalidDer);
+    }
+
+    let int = &data[2..2 + len];
+    // Negative.
+    if int[0] & 0x80 != 0 {
+        return Err(DecodeError::InvalidDer);
+    }
+    // Unnecessary leading zero.
+    if len > 1 && int[0] == 0 && int[1] & 0x80 == 0 {
+        return Err(DecodeError::InvalidDer);
+    }
+    // More than 256 bits.
+    if len == 33 && int[0] != 0 {
+        return Err(DecodeError::InvalidDer);
+    }
+
+    Ok((BigUint::from_bytes_be(int), &data[2 + len..]))
+}
+
+/// Read a lax DER integer at `pos`, or `None` if it does not fit in 32
+/// bytes.
+fn read_integer_lax(data: &[u8], pos: &mut usize) -> Result<Option<BigUint>, DecodeError> {
+    if data.get(*pos) != Some(&0x02) {
+        return Err(DecodeError::InvalidDer);
+    }
+    *pos += 1;
+
+    let lenbyte = *data.get(*pos).ok_or(DecodeError::InvalidDer)?;
+    *pos += 1;
+    let len = if lenbyte & 0x80 != 0 {
+        let mut lenbytes = (lenbyte & 0x7f) as usize;
+        if *pos + lenbytes > data.len() {
+            return Err(DecodeError::InvalidDer);
+        }
+        while lenbytes > 0 && data[*pos] == 0 {
+            *pos += 1;
+            lenbytes -= 1;
+        }
+        if lenbytes > std::mem::size_of::<usize>() {
+            return Err(DecodeError::InvalidDer);
+        }
+        let mut len = 0usize;
+        for _ in 0..lenbytes {
+            len = (len << 8) | data[*pos] as usize;
+            *pos += 1;
+        }
+        len
+    } else {
+        lenbyte as usize
+    };
+    if len > data.len() - *pos {
+        return Err(DecodeError::InvalidDer);
+    }
+
+    let mut int = &data[*pos..*pos + len];
+    *pos += len;
+    while let [0, rest @ ..] = int {
+        int = rest;
+    }
+
+    if int.len() > 32 {
+        Ok(None)
+    } else {
+        Ok(Some(BigUint::from_bytes_be(int)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use num_bigint::BigUint;
-    use num_traits::Num;
+    use num_bigint::{BigUint, RandBigInt};
+    use num_traits::{Num, One, Zero};
+    use rand::{thread_rng, Rng};
+
+    use super::{split_sighash, RecoverableSignature, Signature};
+    use crate::{
+        helper::{hash256, DecodeError},
+        private_key::PrivateKey,
+        Point, PointError, N,
+    };
 
-    use super::Signature;
+    fn random_signature<R: Rng>(rng: &mut R) -> Signature {
+        // Vary the sizes to hit short values and the sign padding.
+        let mut value = || {
+            let bits = rng.gen_range(1..=256);
+            rng.gen_biguint_range(&BigUint::one(), &(BigUint::one() << bits))
+        };
+        Signature::new(value(), value())
+    }
 
     #[test]
     fn test_der_format() {
@@ -76,4 +295,175 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_parse() {
+        let der = hex::decode(
+            "3045022037206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6022100\
+8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec",
+        )
+        .unwrap();
+        let sig = Signature::parse(&der).unwrap();
+        assert_eq!(sig.der(), der);
+        assert_eq!(Signature::parse_lax(&der).unwrap(), sig);
+
+        let mut script_sig = der.clone();
+        script_sig.push(0x01);
+        assert_eq!(
+            Signature::parse_with_sighash(&script_sig).unwrap(),
+            (sig.clone(), 0x01)
+        );
+        assert_eq!(split_sighash(&[]), Err(DecodeError::InvalidLength(0)));
+
+        for invalid in [
+            // Wrong sequence length.
+            "3007020101020102",
+            // Trailing byte.
+            "300702010102010200",
+            // Negative r.
+            "3006020181020101",
+            // Unnecessary padding of s.
+            "300702010102020001",
+            // Zero-length r.
+            "3006020002020101",
+            // Long-form length of r.
+            "300702810101020101",
+            // Not an integer.
+            "3006030101020101",
+        ] {
+            let der = hex::decode(invalid).unwrap();
+            assert!(Signature::parse(&der).is_err(), "{}", invalid);
+        }
+        assert_eq!(
+            Signature::parse(&[0x30, 0x00]),
+            Err(DecodeError::InvalidLength(2))
+        );
+    }
+
+    #[test]
+    fn test_parse_lax() {
+        let one = BigUint::one();
+        let two = BigUint::from(2u8);
+
+        for lax in [
+            // Wrong sequence length and trailing bytes.
+            "30000201010201020000",
+            // Long-form lengths.
+            "3081070282000101020102",
+            // Excess padding and a negative s.
+            "300902030000010201fe",
+        ] {
+            let der = hex::decode(lax).unwrap();
+            assert!(Signature::parse(&der).is_err(), "{}", lax);
+            let sig = Signature::parse_lax(&der).unwrap();
+            assert_eq!(sig.r, one, "{}", lax);
+        }
+        assert_eq!(
+            Signature::parse_lax(&hex::decode("3081070282000101020102").unwrap())
+                .unwrap()
+                .s,
+            two
+        );
+
+        // A 33-byte r gives a zero signature.
+        let mut der = vec![0x30, 0x26, 0x02, 0x21];
+        der.extend([0x01; 33]);
+        der.extend([0x02, 0x01, 0x01]);
+        let sig = Signature::parse_lax(&der).unwrap();
+        assert!(sig.r.is_zero() && sig.s.is_zero());
+
+        // Truncated.
+        assert_eq!(
+            Signature::parse_lax(&hex::decode("3006020101020201").unwrap()),
+            Err(DecodeError::InvalidDer)
+        );
+        assert_eq!(
+            Signature::parse_lax(&hex::decode("30840000").unwrap()),
+            Err(DecodeError::InvalidDer)
+        );
+    }
+
+    #[test]
+    fn test_der_round_trip() {
+        let mut rng = thread_rng();
+        for _ in 0..1000 {
+            let sig = random_signature(&mut rng);
+            let der = sig.der();
+            assert_eq!(Signature::parse(&der).unwrap(), sig);
+            assert_eq!(Signature::parse_lax(&der).unwrap(), sig);
+
+            let sighash = rng.gen();
+            let mut script_sig = der.clone();
+            script_sig.push(sighash);
+            assert_eq!(
+                Signature::parse_with_sighash(&script_sig).unwrap(),
+                (sig, sighash)
+            );
+
+            // Strict parsing rejects every truncation and extension.
+            for len in 0..der.len() {
+                assert!(Signature::parse(&der[..len]).is_err());
+            }
+            assert!(Signature::parse(&script_sig).is_err());
+        }
+    }
+
+    #[test]
+    fn test_recover() {
+        let n = BigUint::from_str_radix(N, 16).unwrap();
+        let mut rng = thread_rng();
+        for _ in 0..20 {
+            let key = PrivateKey::new(rng.gen_biguint_range(&BigUint::one(), &n));
+            let z = rng.gen_biguint(256);
+
+            let (sig, recid) = key.sign_recoverable(z.clone());
+            assert_eq!(sig, key.sign(z.clone()));
+            assert_eq!(Point::recover(&z, &sig, recid).unwrap(), key.point);
+            // The other parity gives another key.
+            assert_ne!(Point::recover(&z, &sig, recid ^ 1).unwrap(), key.point);
+
+            let compressed = rng.gen();
+            let recoverable = RecoverableSignature::new(sig, recid, compressed);
+            let compact = recoverable.serialize_compact();
+            let parsed = RecoverableSignature::parse_compact(&compact).unwrap();
+            assert_eq!(parsed, recoverable);
+            assert_eq!(parsed.recover(&z).unwrap(), key.point);
+        }
+    }
+
+    #[test]
+    fn test_recover_invalid() {
+        let z = BigUint::from_bytes_be(&hash256(b"message"));
+        let (sig, recid) = PrivateKey::new(BigUint::from(12345u32)).sign_recoverable(z.clone());
+
+        assert_eq!(
+            Point::recover(&z, &sig, 4),
+            Err(PointError::InvalidRecoveryId(4))
+        );
+        assert_eq!(
+            Point::recover(&z, &Signature::new(BigUint::zero(), sig.s.clone()), recid),
+            Err(PointError::InvalidSignature)
+        );
+        let n = BigUint::from_str_radix(N, 16).unwrap();
+        assert_eq!(
+            Point::recover(&z, &Signature::new(sig.r.clone(), n), recid),
+            Err(PointError::InvalidSignature)
+        );
+        // r + n is above the field size for almost all r.
+        assert_eq!(
+            Point::recover(&z, &sig, recid | 2),
+            Err(PointError::NotOnCurve)
+        );
+
+        let mut compact = RecoverableSignature::new(sig, recid, true).serialize_compact();
+        assert_eq!(
+            RecoverableSignature::parse_compact(&compact[..64]),
+            Err(DecodeError::InvalidLength(64))
+        );
+        compact[0] = 35;
+        assert_eq!(
+            RecoverableSignature::parse_compact(&compact),
+            Err(DecodeError::InvalidVersion(35))
+        );
+    }
 }
@@ -1,10 +1,182 @@
-use std::ops::{Add, Div, Mul, Sub};
+use std::{
+    fmt::Debug,
+    ops::{Add, Div, Mul, Sub},
+};
 
 use num_bigint::{BigInt, BigUint};
-use num_traits::{FromPrimitive, Num, One, Zero};
+use num_traits::{Num, One, Zero};
+
+pub mod montgomery;
+
+pub use montgomery::{Fp256, Modulus256, P256Field, Secp256k1Field};
 
 pub const P: &str = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F";
 
+/// Arithmetic in a field of prime order.
+///
+/// Implemented by `FieldElement`, which carries its modulus at runtime, and
+/// by `Fp256`, whose modulus is fixed by its type. Elements of a field are
+/// created from an existing one with `element`, since a runtime modulus has
+/// to come from somewhere.
+pub trait PrimeField:
+    Sized
+    + Clone
+    + Debug
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    /// The prime order of the field.
+    fn modulus(&self) -> BigUint;
+
+    /// The value of the element, in the range 0 to modulus - 1.
+    fn value(&self) -> BigUint;
+
+    /// The element `num` mod the modulus, in the same field as `self`.
+    fn element(&self, num: BigUint) -> Self;
+
+    fn is_zero(&self) -> bool {
+        self.value().is_zero()
+    }
+
+    fn square(&self) -> Self {
+        self.clone() * self.clone()
+    }
+
+    fn power(&self, exponent: &BigUint) -> Self {
+        let mut result = self.element(BigUint::one());
+        for i in (0..exponent.bits()).rev() {
+            result = result.square();
+            if exponent.bit(i) {
+                result = result * self.clone();
+            }
+        }
+        result
+    }
+
+    /// The multiplicative inverse, or `None` for zero.
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        // Fermat's little theorem: 1/n == n^(p-2).
+        Some(self.power(&(self.modulus() - 2u32)))
+    }
+
+    /// The Legendre symbol: 0 for zero, 1 for a non-zero square and -1
+    /// otherwise. The modulus must be an odd prime.
+    fn legendre(&self) -> i8 {
+        if self.is_zero() {
+            return 0;
+        }
+        // Euler's criterion: n^((p-1)/2) is 1 for squares and -1 otherwise.
+        let symbol = self.power(&((self.modulus() - 1u32) >> 1));
+        if symbol == self.element(BigUint::one()) {
+            1
+        } else {
+            -1
+        }
+    }
+
+    fn is_square(&self) -> bool {
+        self.modulus() == BigUint::from(2u8) || self.legendre() >= 0
+    }
+
+    /// A square root with Tonelli-Shanks, or `None` if there is none. The
+    /// other root is its negation.
+    fn sqrt(&self) -> Option<Self> {
+        let p = self.modulus();
+        if self.is_zero() || p == BigUint::from(2u8) {
+            return Some(self.clone());
+        }
+        if self.legendre() != 1 {
+            return None;
+        }
+
+        // p - 1 = q * 2^s with q odd.
+        let s = (&p - 1u32).trailing_zeros().unwrap();
+        let q = (&p - 1u32) >> s;
+        let one = self.element(BigUint::one());
+
+        // p = 3 mod 4 has the direct root n^((p+1)/4).
+        if s == 1 {
+            return Some(self.power(&((&p + 1u32) >> 2)));
+        }
+
+        let mut z = BigUint::from(2u8);
+        while self.element(z.clone()).legendre() != -1 {
+            z += 1u32;
+        }
+
+        let mut m = s;
+        let mut c = self.element(z).power(&q);
+        let mut t = self.power(&q);
+        let mut r = self.power(&((&q + 1u32) >> 1));
+        // Invariant: r^2 = n * t, with t of order dividing 2^(m-1).
+        while t != one {
+            let mut i = 0;
+            let mut t2i = t.clone();
+            while t2i != one {
+                t2i = t2i.square();
+                i += 1;
+            }
+
+            let mut b = c;
+            for _ in 0..m - i - 1 {
+                b = b.square();
+            }
+            m = i;
+            c = b.square();
+            t = t * c.clone();
+            r = r * b;
+        }
+        Some(r)
+    }
+
+    /// A square root with Cipolla's algorithm, or `None` if there is none.
+    /// Gives the same roots as `sqrt`, not necessarily the same one of the
+    /// two.
+    fn sqrt_cipolla(&self) -> Option<Self> {
+        let p = self.modulus();
+        if self.is_zero() || p == BigUint::from(2u8) {
+            return Some(self.clone());
+        }
+        if self.legendre() != 1 {
+            return None;
+        }
+
+        // Find a with a^2 - n not a square, and work in F_p(w) with
+        // w^2 = a^2 - n.
+        let mut a = self.element(BigUint::one());
+        let mut w2 = a.square() - self.clone();
+        while w2.legendre() != -1 {
+            a = a + self.element(BigUint::one());
+            w2 = a.square() - self.clone();
+        }
+
+        let mul = |(x1, y1): (Self, Self), (x2, y2): (Self, Self)| {
+            (
+                x1.clone() * x2.clone() + y1.clone() * y2.clone() * w2.clone(),
+                x1 * y2 + x2 * y1,
+            )
+        };
+
+        // (a + w)^((p+1)/2) lies in F_p.
+        let exponent: BigUint = (p + 1u32) >> 1;
+        let mut result = (self.element(BigUint::one()), self.element(BigUint::zero()));
+        let base = (a, self.element(BigUint::one()));
+        for i in (0..exponent.bits()).rev() {
+            result = mul(result.clone(), result);
+            if exponent.bit(i) {
+                result = mul(result, base.clone());
+            }
+        }
+        Some(result.0)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FieldElement {
     pub num: BigUint,
@@ -91,10 +263,22 @@ impl FieldElement {
             result
         }
     }
+}
+
+impl PrimeField for FieldElement {
+    fn modulus(&self) -> BigUint {
+        self.prime.clone()
+    }
+
+    fn value(&self) -> BigUint {
+        self.num.clone()
+    }
 
-    pub fn sqrt(&self) -> Self {
-        let p = BigUint::from_str_radix(P, 16).unwrap();
-        self.to_the_power_of((p + BigUint::one()) / (BigUint::from_u8(4).unwrap()))
+    fn element(&self, num: BigUint) -> Self {
+        Self {
+            num: self.modulo(&num),
+            prime: self.prime.clone(),
+        }
     }
 }
 
@@ -286,4 +470,78 @@ mod tests {
 
         assert_eq!(a / b, c);
     }
+
+    #[test]
+    fn test_sqrt_small_primes() {
+        // Both p = 3 mod 4 and p = 1 mod 4, up to 2^4 | p - 1.
+        for p in [3u32, 5, 7, 11, 13, 17, 41, 97, 113, 257, 401] {
+            let prime = Some(biguint!(p));
+            let squares: Vec<u32> = (0..p).map(|n| n * n % p).collect();
+
+            for n in 0..p {
+                let element = FieldElement::new(biguint!(n), prime.clone());
+                let is_square = squares.contains(&n);
+                assert_eq!(element.is_square(), is_square, "{} mod {}", n, p);
+                assert_eq!(
+                    element.legendre(),
+                    if n == 0 {
+                        0
+                    } else if is_square {
+                        1
+                    } else {
+                        -1
+                    }
+                );
+
+                for root in [element.sqrt(), element.sqrt_cipolla()] {
+                    match root {
+                        Some(root) => assert_eq!(root.square(), element, "{} mod {}", n, p),
+                        None => assert!(!is_square, "{} mod {}", n, p),
+                    }
+                }
+            }
+        }
+
+        let two = FieldElement::new(biguint!(1), Some(biguint!(2)));
+        assert_eq!(two.sqrt(), Some(two.clone()));
+    }
+
+    #[test]
+    fn test_sqrt_large_primes() {
+        // P-256 is 3 mod 4, P-224 has 2^96 | p - 1.
+        let p256 = "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff";
+        let p224 = "ffffffffffffffffffffffffffffffff000000000000000000000001";
+
+        for p in [P, p256, p224] {
+            let prime = BigUint::from_str_radix(p, 16).unwrap();
+            for n in [2u32, 3, 5, 7, 11, 12345] {
+                let root = FieldElement::new(biguint!(n), Some(prime.clone()));
+                let square = root.square();
+                assert!(square.is_square());
+
+                for found in [square.sqrt().unwrap(), square.sqrt_cipolla().unwrap()] {
+                    assert!(
+                        found == root
+                            || found.clone() + root.clone() == root.element(BigUint::zero())
+                    );
+                }
+
+                // -1 is not a square when p = 3 mod 4, so -n^2 is not
+                // either.
+                let negated = root.element(BigUint::zero()) - square;
+                if prime.bit(1) {
+                    assert_eq!(negated.legendre(), -1);
+                    assert_eq!(negated.sqrt(), None);
+                    assert_eq!(negated.sqrt_cipolla(), None);
+                } else {
+                    assert_eq!(negated.sqrt().unwrap().square(), negated);
+                }
+            }
+        }
+
+        // The same through the Montgomery implementation.
+        let root = P256Field::from_u64(12345);
+        assert_eq!(root.square().sqrt().unwrap().square(), root.square());
+        assert_eq!((P256Field::zero() - root.square()).sqrt(), None);
+    }
 }
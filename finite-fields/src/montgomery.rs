@@ -0,0 +1,426 @@
+//! Fixed-width arithmetic for 256-bit prime fields in Montgomery form.
+//!
+//! An element a is stored as aR mod p with R = 2^256, as four 64-bit limbs,
+//! least significant first. Multiplication then reduces with shifts instead
+//! of a division, and no element needs an allocation.
+
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Sub},
+};
+
+use num_bigint::BigUint;
+
+use crate::PrimeField;
+
+type Limbs = [u64; 4];
+
+/// The modulus of an `Fp256` field, an odd prime below 2^256.
+pub trait Modulus256: 'static {
+    /// The prime, least significant limb first.
+    const MODULUS: Limbs;
+}
+
+/// The field of secp256k1 coordinates, 2^256 - 2^32 - 977.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256k1P;
+
+impl Modulus256 for Secp256k1P {
+    const MODULUS: Limbs = [
+        0xfffffffefffffc2f,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ];
+}
+
+/// The field of NIST P-256 coordinates, 2^256 - 2^224 + 2^192 + 2^96 - 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct P256P;
+
+impl Modulus256 for P256P {
+    const MODULUS: Limbs = [
+        0xffffffffffffffff,
+        0x00000000ffffffff,
+        0x0000000000000000,
+        0xffffffff00000001,
+    ];
+}
+
+pub type Secp256k1Field = Fp256<Secp256k1P>;
+pub type P256Field = Fp256<P256P>;
+
+/// `a + b * c + carry`, as a low and a high limb.
+const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + (b as u128) * (c as u128) + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+const fn geq(a: &Limbs, b: &Limbs) -> bool {
+    let mut i = 4;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+/// `a - b`, and whether it borrowed.
+const fn sub_limbs(a: &Limbs, b: &Limbs) -> (Limbs, bool) {
+    let mut result = [0u64; 4];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < 4 {
+        let (d, b1) = a[i].overflowing_sub(b[i]);
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        result[i] = d;
+        borrow = b1 || b2;
+        i += 1;
+    }
+    (result, borrow)
+}
+
+/// `a + b`, and whether it carried.
+const fn add_limbs(a: &Limbs, b: &Limbs) -> (Limbs, bool) {
+    let mut result = [0u64; 4];
+    let mut carry = false;
+    let mut i = 0;
+    while i < 4 {
+        let (s, c1) = a[i].overflowing_add(b[i]);
+        let (s, c2) = s.overflowing_add(carry as u64);
+        result[i] = s;
+        carry = c1 || c2;
+        i += 1;
+    }
+    (result, carry)
+}
+
+/// `a + b mod p`, for `a` and `b` below `p`.
+const fn add_mod(a: &Limbs, b: &Limbs, p: &Limbs) -> Limbs {
+    let (sum, carry) = add_limbs(a, b);
+    if carry || geq(&sum, p) {
+        sub_limbs(&sum, p).0
+    } else {
+        sum
+    }
+}
+
+/// -p^-1 mod 2^64, by Newton's iteration.
+const fn compute_inv(p0: u64) -> u64 {
+    let mut inv = 1u64;
+    let mut i = 0;
+    while i < 6 {
+        // Each step doubles the number of correct low bits.
+        inv = inv.wrapping_mul(2u64.wrapping_sub(p0.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv.wrapping_neg()
+}
+
+/// R^2 mod p, by doubling 1 modulo p 512 times.
+const fn compute_r2(p: &Limbs) -> Limbs {
+    let mut r = [1u64, 0, 0, 0];
+    let mut i = 0;
+    while i < 512 {
+        r = add_mod(&r, &r, p);
+        i += 1;
+    }
+    r
+}
+
+fn to_limbs(n: &BigUint) -> Limbs {
+    let mut limbs = [0u64; 4];
+    for (limb, digit) in limbs.iter_mut().zip(n.iter_u64_digits()) {
+        *limb = digit;
+    }
+    limbs
+}
+
+fn from_limbs(limbs: &Limbs) -> BigUint {
+    let mut bytes = Vec::with_capacity(32);
+    for limb in limbs {
+        bytes.extend_from_slice(&limb.to_le_bytes());
+    }
+    BigUint::from_bytes_le(&bytes)
+}
+
+/// An element of the prime field of `M`, in Montgomery form.
+pub struct Fp256<M: Modulus256> {
+    limbs: Limbs,
+    modulus: PhantomData<M>,
+}
+
+impl<M: Modulus256> Fp256<M> {
+    const INV: u64 = compute_inv(M::MODULUS[0]);
+    const R2: Limbs = compute_r2(&M::MODULUS);
+
+    const fn from_montgomery(limbs: Limbs) -> Self {
+        Self {
+            limbs,
+            modulus: PhantomData,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::from_montgomery([0; 4])
+    }
+
+    pub fn one() -> Self {
+        Self::from_montgomery(Self::mont_mul(&[1, 0, 0, 0], &Self::R2))
+    }
+
+    /// The element `num` mod p.
+    pub fn new(num: &BigUint) -> Self {
+        let reduced = num % from_limbs(&M::MODULUS);
+        Self::from_montgomery(Self::mont_mul(&to_limbs(&reduced), &Self::R2))
+    }
+
+    pub fn from_u64(num: u64) -> Self {
+        Self::new(&BigUint::from(num))
+    }
+
+    /// Montgomery multiplication, `a * b / R mod p` (CIOS).
+    fn mont_mul(a: &Limbs, b: &Limbs) -> Limbs {
+        let p = &M::MODULUS;
+        // Four limbs of the running total, plus two for its carries.
+        let mut t = [0u64; 6];
+        for &bi in b {
+            let mut carry = 0;
+            for j in 0..4 {
+                (t[j], carry) = mac(t[j], a[j], bi, carry);
+            }
+            let (t4, c) = t[4].overflowing_add(carry);
+            t[4] = t4;
+            t[5] = c as u64;
+
+            // Add a multiple of p that clears the low limb, then shift it
+            // out.
+            let m = t[0].wrapping_mul(Self::INV);
+            let (_, mut carry) = mac(t[0], m, p[0], 0);
+            for j in 1..4 {
+                (t[j - 1], carry) = mac(t[j], m, p[j], carry);
+            }
+            let (t3, c) = t[4].overflowing_add(carry);
+            t[3] = t3;
+            t[4] = t[5] + c as u64;
+        }
+
+        // The total is below 2p.
+        let result = [t[0], t[1], t[2], t[3]];
+        if t[4] != 0 || geq(&result, p) {
+            sub_limbs(&result, p).0
+        } else {
+            result
+        }
+    }
+}
+
+impl<M: Modulus256> Clone for Fp256<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: Modulus256> Copy for Fp256<M> {}
+
+impl<M: Modulus256> PartialEq for Fp256<M> {
+    fn eq(&self, other: &Self) -> bool {
+        // Elements are always fully reduced.
+        self.limbs == other.limbs
+    }
+}
+
+impl<M: Modulus256> Eq for Fp256<M> {}
+
+impl<M: Modulus256> fmt::Debug for Fp256<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fp256({:#x})", self.value())
+    }
+}
+
+impl<M: Modulus256> PrimeField for Fp256<M> {
+    fn modulus(&self) -> BigUint {
+        from_limbs(&M::MODULUS)
+    }
+
+    fn value(&self) -> BigUint {
+        from_limbs(&Self::mont_mul(&self.limbs, &[1, 0, 0, 0]))
+    }
+
+    fn element(&self, num: BigUint) -> Self {
+        Self::new(&num)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.limbs == [0; 4]
+    }
+}
+
+impl<M: Modulus256> Add for Fp256<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_montgomery(add_mod(&self.limbs, &rhs.limbs, &M::MODULUS))
+    }
+}
+
+impl<M: Modulus256> Sub for Fp256<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (difference, borrow) = sub_limbs(&self.limbs, &rhs.limbs);
+        if borrow {
+            Self::from_montgomery(add_limbs(&difference, &M::MODULUS).0)
+        } else {
+            Self::from_montgomery(difference)
+        }
+    }
+}
+
+impl<M: Modulus256> Mul for Fp256<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::from_montgomery(Self::mont_mul(&self.limbs, &rhs.limbs))
+    }
+}
+
+impl<M: Modulus256> Div for Fp256<M> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let inverse = rhs.inverse().expect("division by zero");
+        Self::from_montgomery(Self::mont_mul(&self.limbs, &inverse.limbs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use num_traits::{Num, One, Zero};
+    use rand::{thread_rng, Rng};
+
+    use super::{compute_inv, P256Field, Secp256k1Field, Secp256k1P};
+    use crate::{FieldElement, Modulus256, PrimeField, P};
+
+    const P256: &str = "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff";
+
+    fn random_biguint<R: Rng>(rng: &mut R) -> BigUint {
+        BigUint::from_bytes_be(&rng.gen::<[u8; 32]>())
+    }
+
+    /// Field axioms, checked through the trait only.
+    fn check_field<F: PrimeField>(a: F, b: F, c: F) {
+        let zero = a.element(BigUint::zero());
+        let one = a.element(BigUint::one());
+
+        assert_eq!(a.clone() + zero.clone(), a);
+        assert_eq!(a.clone() * one.clone(), a);
+        assert_eq!(a.clone() - a.clone(), zero);
+        assert_eq!(a.clone() + b.clone(), b.clone() + a.clone());
+        assert_eq!(a.clone() * b.clone(), b.clone() * a.clone());
+        assert_eq!(
+            a.clone() * (b.clone() + c.clone()),
+            a.clone() * b.clone() + a.clone() * c.clone()
+        );
+        assert_eq!((a.clone() - b.clone()) + b.clone(), a);
+        assert_eq!(a.square(), a.clone() * a.clone());
+        if !b.is_zero() {
+            assert_eq!((a.clone() / b.clone()) * b.clone(), a);
+            assert_eq!(b.inverse().unwrap() * b.clone(), one);
+        }
+        // Fermat's little theorem.
+        if !a.is_zero() {
+            assert_eq!(a.power(&(a.modulus() - 1u32)), one);
+        }
+        assert!(zero.inverse().is_none());
+    }
+
+    #[test]
+    fn test_constants() {
+        let p0 = Secp256k1P::MODULUS[0];
+        assert_eq!(p0.wrapping_mul(compute_inv(p0)), u64::MAX);
+        assert_eq!(
+            Secp256k1Field::one().modulus(),
+            BigUint::from_str_radix(P, 16).unwrap()
+        );
+        assert_eq!(
+            P256Field::one().modulus(),
+            BigUint::from_str_radix(P256, 16).unwrap()
+        );
+        assert_eq!(Secp256k1Field::one().value(), BigUint::one());
+        assert!(Secp256k1Field::zero().is_zero());
+    }
+
+    #[test]
+    fn test_against_field_element() {
+        let mut rng = thread_rng();
+        for modulus in [P, P256] {
+            let p = BigUint::from_str_radix(modulus, 16).unwrap();
+            for _ in 0..100 {
+                let (x, y) = (random_biguint(&mut rng) % &p, random_biguint(&mut rng) % &p);
+                let (a, b) = (
+                    FieldElement::new(x.clone(), Some(p.clone())),
+                    FieldElement::new(y.clone(), Some(p.clone())),
+                );
+
+                let expected = [
+                    (a.clone() + b.clone()).num,
+                    (a.clone() - b.clone()).num,
+                    (a.clone() * b.clone()).num,
+                    (a.clone() / b.clone()).num,
+                ];
+                let actual = if modulus == P {
+                    let (a, b) = (Secp256k1Field::new(&x), Secp256k1Field::new(&y));
+                    assert_eq!(a.value(), x);
+                    [a + b, a - b, a * b, a / b].map(|v| v.value())
+                } else {
+                    let (a, b) = (P256Field::new(&x), P256Field::new(&y));
+                    assert_eq!(a.value(), x);
+                    [a + b, a - b, a * b, a / b].map(|v| v.value())
+                };
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_edge_values() {
+        let p = BigUint::from_str_radix(P, 16).unwrap();
+        let max = Secp256k1Field::new(&(&p - 1u32));
+        let one = Secp256k1Field::one();
+
+        assert!((max + one).is_zero());
+        assert_eq!(Secp256k1Field::zero() - one, max);
+        assert_eq!(max * max, one);
+        // Values at and above p are reduced.
+        assert!(Secp256k1Field::new(&p).is_zero());
+        assert_eq!(
+            Secp256k1Field::new(&((BigUint::one() << 256) - 1u32)).value(),
+            (BigUint::one() << 256) - 1u32 - &p
+        );
+    }
+
+    #[test]
+    fn test_prime_field() {
+        let mut rng = thread_rng();
+        for _ in 0..20 {
+            let [a, b, c] = [0; 3].map(|_| Secp256k1Field::new(&random_biguint(&mut rng)));
+            check_field(a, b, c);
+            let [a, b, c] = [0; 3].map(|_| P256Field::new(&random_biguint(&mut rng)));
+            check_field(a, b, c);
+
+            let prime = BigUint::from(1_000_003u32);
+            let [a, b, c] = [0; 3].map(|_| {
+                FieldElement::new(
+                    BigUint::from(rng.gen_range(0..1_000_003u32)),
+                    Some(prime.clone()),
+                )
+            });
+            check_field(a, b, c);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! `SharedSecret` against `secp256k1_ecdh` with the default hash function.
+//!
+//! Input: 33 bytes compressed public key, 32 bytes secret key.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sha2::Sha256;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() != 65 {
+        return;
+    }
+    let (pubkey, seckey) = data.split_at(33);
+
+    let (pubkey, secp_pubkey) = match (
+        lib::PublicKey::parse_slice(pubkey, None),
+        secp256k1::PublicKey::from_slice(pubkey),
+    ) {
+        (Ok(ours), Ok(theirs)) => (ours, theirs),
+        (Err(_), Err(_)) => return,
+        (ours, theirs) => panic!("public key mismatch: {:?} vs {:?}", ours, theirs),
+    };
+    let (seckey, secp_seckey) = match (
+        lib::SecretKey::parse_slice(seckey),
+        secp256k1::SecretKey::from_slice(seckey),
+    ) {
+        (Ok(ours), Ok(theirs)) => (ours, theirs),
+        (Err(_), Err(_)) => return,
+        (ours, theirs) => panic!("secret key mismatch: {:?} vs {:?}", ours, theirs),
+    };
+
+    let ours = lib::SharedSecret::<Sha256>::new(&pubkey, &seckey).unwrap();
+    let theirs = secp256k1::ecdh::SharedSecret::new(&secp_pubkey, &secp_seckey);
+    assert_eq!(ours.as_ref(), &theirs[..]);
+});
@@ -0,0 +1,31 @@
+//! `Signature::parse_der` and `Signature::parse_der_lax` against
+//! `secp256k1_ecdsa_signature_parse_der` and `ecdsa_signature_parse_der_lax`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fn check(
+    ours: Result<lib::Signature, lib::Error>,
+    theirs: Result<secp256k1::Signature, secp256k1::Error>,
+) {
+    match (ours, theirs) {
+        (Ok(ours), Ok(theirs)) => {
+            assert_eq!(ours.serialize(), theirs.serialize_compact());
+            assert_eq!(ours.serialize_der().as_ref(), &theirs.serialize_der()[..]);
+        }
+        (Err(_), Err(_)) => (),
+        (ours, theirs) => panic!("mismatch: {:?} vs {:?}", ours, theirs),
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    check(
+        lib::Signature::parse_der(data),
+        secp256k1::Signature::from_der(data),
+    );
+    check(
+        lib::Signature::parse_der_lax(data),
+        secp256k1::Signature::from_der_lax(data),
+    );
+});
@@ -0,0 +1,24 @@
+//! `PublicKey::parse_slice` against `secp256k1_ec_pubkey_parse`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // 64 bytes raw keys are an extension of this library.
+    if data.len() == 64 {
+        return;
+    }
+
+    let ours = lib::PublicKey::parse_slice(data, None);
+    let theirs = secp256k1::PublicKey::from_slice(data);
+
+    match (ours, theirs) {
+        (Ok(ours), Ok(theirs)) => {
+            assert_eq!(ours.serialize(), theirs.serialize_uncompressed());
+            assert_eq!(ours.serialize_compressed(), theirs.serialize());
+        }
+        (Err(_), Err(_)) => (),
+        (ours, theirs) => panic!("mismatch: {:?} vs {:?}", ours, theirs),
+    }
+});
@@ -0,0 +1,46 @@
+//! `recover` against `secp256k1_ecdsa_recover`.
+//!
+//! Input: 32 bytes message, a recovery id byte, then a 64 bytes compact
+//! signature.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use secp256k1::recovery::{RecoverableSignature, RecoveryId};
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() != 97 {
+        return;
+    }
+    let recid = data[32] & 0x03;
+
+    let mut array = [0u8; 32];
+    array.copy_from_slice(&data[..32]);
+    let message = lib::Message::parse(&array);
+    let secp_message = secp256k1::Message::from_slice(&array).unwrap();
+
+    let (signature, secp_signature) = match (
+        lib::Signature::parse_standard_slice(&data[33..]),
+        RecoverableSignature::from_compact(
+            &data[33..],
+            RecoveryId::from_i32(recid as i32).unwrap(),
+        ),
+    ) {
+        (Ok(ours), Ok(theirs)) => (ours, theirs),
+        (Err(_), Err(_)) => return,
+        (ours, theirs) => panic!("signature mismatch: {:?} vs {:?}", ours, theirs),
+    };
+
+    let ours = lib::recover(
+        &message,
+        &signature,
+        &lib::RecoveryId::parse(recid).unwrap(),
+    );
+    let theirs = secp256k1::Secp256k1::verification_only().recover(&secp_message, &secp_signature);
+
+    match (ours, theirs) {
+        (Ok(ours), Ok(theirs)) => assert_eq!(ours.serialize(), theirs.serialize_uncompressed()),
+        (Err(_), Err(_)) => (),
+        (ours, theirs) => panic!("mismatch: {:?} vs {:?}", ours, theirs),
+    }
+});
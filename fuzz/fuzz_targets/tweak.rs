@@ -0,0 +1,67 @@
+//! Secret and public key tweaks against `secp256k1_ec_seckey_tweak_*` and
+//! `secp256k1_ec_pubkey_tweak_*`.
+//!
+//! Input: 32 bytes secret key, 32 bytes tweak.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() != 64 {
+        return;
+    }
+    let (seckey, tweak) = data.split_at(32);
+
+    let (seckey, secp_seckey) = match (
+        lib::SecretKey::parse_slice(seckey),
+        secp256k1::SecretKey::from_slice(seckey),
+    ) {
+        (Ok(ours), Ok(theirs)) => (ours, theirs),
+        (Err(_), Err(_)) => return,
+        (ours, theirs) => panic!("secret key mismatch: {:?} vs {:?}", ours, theirs),
+    };
+    // Tweaks are secret keys in this library, so zero is never a valid tweak.
+    let tweak = match lib::SecretKey::parse_slice(tweak) {
+        Ok(tweak) => tweak,
+        Err(_) => return,
+    };
+    let tweak_bytes = tweak.serialize();
+
+    let secp256k1 = secp256k1::Secp256k1::new();
+    let pubkey = lib::PublicKey::from_secret_key(&seckey);
+    let secp_pubkey = secp256k1::PublicKey::from_secret_key(&secp256k1, &secp_seckey);
+
+    // The C library clears the key when a tweak fails.
+    let mut ours = seckey;
+    let mut theirs = secp_seckey;
+    let ok = ours.tweak_add_assign(&tweak).is_ok();
+    assert_eq!(ok, theirs.add_assign(&tweak_bytes).is_ok());
+    if ok {
+        assert_eq!(ours.serialize()[..], theirs[..]);
+    }
+
+    let mut ours = seckey;
+    let mut theirs = secp_seckey;
+    let ok = ours.tweak_mul_assign(&tweak).is_ok();
+    assert_eq!(ok, theirs.mul_assign(&tweak_bytes).is_ok());
+    if ok {
+        assert_eq!(ours.serialize()[..], theirs[..]);
+    }
+
+    let mut ours = pubkey;
+    let mut theirs = secp_pubkey;
+    let ok = ours.tweak_add_assign(&tweak).is_ok();
+    assert_eq!(ok, theirs.add_exp_assign(&secp256k1, &tweak_bytes).is_ok());
+    if ok {
+        assert_eq!(ours.serialize(), theirs.serialize_uncompressed());
+    }
+
+    let mut ours = pubkey;
+    let mut theirs = secp_pubkey;
+    let ok = ours.tweak_mul_assign(&tweak).is_ok();
+    assert_eq!(ok, theirs.mul_assign(&secp256k1, &tweak_bytes).is_ok());
+    if ok {
+        assert_eq!(ours.serialize(), theirs.serialize_uncompressed());
+    }
+});
@@ -0,0 +1,49 @@
+//! `verify` against `secp256k1_ecdsa_verify`.
+//!
+//! Input: 32 bytes message, 33 bytes compressed public key, then a DER
+//! signature.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 65 {
+        return;
+    }
+    let (message, rest) = data.split_at(32);
+    let (pubkey, der) = rest.split_at(33);
+
+    let (ours, theirs) = match (
+        lib::PublicKey::parse_slice(pubkey, None),
+        secp256k1::PublicKey::from_slice(pubkey),
+    ) {
+        (Ok(ours), Ok(theirs)) => (ours, theirs),
+        (Err(_), Err(_)) => return,
+        (ours, theirs) => panic!("public key mismatch: {:?} vs {:?}", ours, theirs),
+    };
+    let (signature, mut secp_signature) = match (
+        lib::Signature::parse_der(der),
+        secp256k1::Signature::from_der(der),
+    ) {
+        (Ok(ours), Ok(theirs)) => (ours, theirs),
+        (Err(_), Err(_)) => return,
+        (ours, theirs) => panic!("signature mismatch: {:?} vs {:?}", ours, theirs),
+    };
+
+    let mut array = [0u8; 32];
+    array.copy_from_slice(message);
+    let message = lib::Message::parse(&array);
+    let secp_message = secp256k1::Message::from_slice(&array).unwrap();
+
+    // The C library only accepts low S signatures, this library accepts both.
+    secp_signature.normalize_s();
+
+    let secp256k1 = secp256k1::Secp256k1::verification_only();
+    assert_eq!(
+        lib::verify(&message, &signature, &ours),
+        secp256k1
+            .verify(&secp_message, &secp_signature, &theirs)
+            .is_ok()
+    );
+});
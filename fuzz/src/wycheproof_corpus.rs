@@ -0,0 +1,115 @@
+//! Write a seed corpus for every fuzz target from the Wycheproof ECDSA
+//! secp256k1 vectors, `ecdsa_secp256k1_sha256_test.json` from
+//! https://github.com/C2SP/wycheproof. The vectors are not vendored, so the
+//! path is required.
+//!
+//! ```text
+//! cargo run --bin wycheproof_corpus -- /path/to/ecdsa_secp256k1_sha256_test.json
+//! ```
+
+use std::{env, fs, path::Path};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestCollection {
+    test_groups: Vec<TestGroup>,
+}
+
+#[derive(Deserialize)]
+struct TestGroup {
+    key: TestKey,
+    tests: Vec<TestUnit>,
+}
+
+#[derive(Deserialize)]
+struct TestKey {
+    uncompressed: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestUnit {
+    tc_id: usize,
+    msg: String,
+    sig: String,
+}
+
+fn write(target: &str, name: &str, data: &[u8]) {
+    let dir = Path::new("corpus").join(target);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(name), data).unwrap();
+}
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .expect("usage: wycheproof_corpus <ecdsa_secp256k1_sha256_test.json>");
+    let vectors = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    let collection: TestCollection = serde_json::from_str(&vectors).unwrap();
+
+    let mut count = 0;
+    for (i, group) in collection.test_groups.iter().enumerate() {
+        let uncompressed = hex::decode(&group.key.uncompressed).unwrap();
+        write(
+            "parse_public_key",
+            &format!("wycheproof-key-{}", i),
+            &uncompressed,
+        );
+        let pubkey = lib::PublicKey::parse_slice(&uncompressed, None).unwrap();
+        let compressed = pubkey.serialize_compressed();
+        write(
+            "parse_public_key",
+            &format!("wycheproof-key-{}-compressed", i),
+            &compressed,
+        );
+
+        for test in &group.tests {
+            let name = format!("wycheproof-{}", test.tc_id);
+            let message = Sha256::digest(hex::decode(&test.msg).unwrap());
+            let der = hex::decode(&test.sig).unwrap();
+            write("parse_der", &name, &der);
+
+            let mut data = message.to_vec();
+            data.extend_from_slice(&compressed);
+            data.extend_from_slice(&der);
+            write("verify", &name, &data);
+
+            if let Ok(signature) = lib::Signature::parse_der_lax(&der) {
+                let signature = signature.serialize();
+                for recid in 0..4u8 {
+                    let mut data = message.to_vec();
+                    data.push(recid);
+                    data.extend_from_slice(&signature);
+                    write("recover", &format!("{}-{}", name, recid), &data);
+                }
+
+                // The vectors have no secret keys: tweak the message hash by
+                // each signature value.
+                for (value, tweak) in ["r", "s"].iter().zip(signature.chunks(32)) {
+                    let mut data = message.to_vec();
+                    data.extend_from_slice(tweak);
+                    write("tweak", &format!("{}-{}", name, value), &data);
+                }
+            }
+
+            let mut data = compressed.to_vec();
+            data.extend_from_slice(&message);
+            write("ecdh", &name, &data);
+            count += 1;
+        }
+    }
+
+    // Adding n - 1 to the secret key 1 gives zero, which both libraries
+    // reject.
+    let mut data = vec![0u8; 31];
+    data.push(1);
+    data.extend(
+        hex::decode("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140").unwrap(),
+    );
+    write("tweak", "zero-sum", &data);
+
+    println!("wrote seeds for {} test vectors", count);
+}
@@ -1,6 +1,9 @@
 #![allow(clippy::needless_range_loop)]
 
-use libsecp256k1_core::curve::ECMultGenContext;
+use libsecp256k1_core::{
+    curve::ECMultGenContext,
+    util::{ECMULT_GEN_PREC_G, ECMULT_GEN_PREC_N},
+};
 use std::{
     fs::File,
     io::{Error, Write},
@@ -11,9 +14,9 @@ pub fn generate_to(file: &mut File) -> Result<(), Error> {
     let prec = context.inspect_raw().as_ref();
 
     file.write_fmt(format_args!("["))?;
-    for j in 0..64 {
+    for j in 0..ECMULT_GEN_PREC_N {
         file.write_fmt(format_args!("    ["))?;
-        for i in 0..16 {
+        for i in 0..ECMULT_GEN_PREC_G {
             let pg = prec[j][i];
             file.write_fmt(format_args!(
                 "        crate::curve::AffineStorage::new(crate::curve::FieldStorage::new({}, {}, {}, {}, {}, {}, {}, {}), crate::curve::FieldStorage::new({}, {}, {}, {}, {}, {}, {}, {})),",
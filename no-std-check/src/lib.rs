@@ -0,0 +1,40 @@
+//! Exercises the public API of libsecp256k1 from a `no_std` crate without
+//! an allocator, using only the static contexts.
+
+#![no_std]
+
+use lib::{
+    curve::{Affine, Jacobian},
+    recover, sign,
+    util::set_all_gej_var,
+    verify, Error, Message, PublicKey, SecretKey,
+};
+
+pub fn sign_and_verify(seckey: &[u8; 32], message: &[u8; 32]) -> Result<bool, Error> {
+    let seckey = SecretKey::parse(seckey)?;
+    let pubkey = PublicKey::from_secret_key(&seckey);
+    let message = Message::parse(message);
+
+    let (signature, recovery_id) = sign(&message, &seckey);
+    let recovered = recover(&message, &signature, &recovery_id)?;
+
+    Ok(recovered == pubkey && verify(&message, &signature, &pubkey))
+}
+
+pub fn serialize_der(
+    seckey: &[u8; 32],
+    message: &[u8; 32],
+    out: &mut [u8; 72],
+) -> Result<usize, Error> {
+    let seckey = SecretKey::parse(seckey)?;
+    let (signature, _) = sign(&Message::parse(message), &seckey);
+    let der = signature.serialize_der();
+    out[..der.len()].copy_from_slice(der.as_ref());
+    Ok(der.len())
+}
+
+pub fn normalize_all(points: &[Jacobian; 4]) -> [Affine; 4] {
+    let mut r = [Affine::default(); 4];
+    set_all_gej_var(&mut r, points);
+    r
+}
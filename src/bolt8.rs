@@ -0,0 +1,383 @@
+//! Lightning transport encryption, as specified in BOLT8.
+//!
+//! The handshake is `Noise_XK_secp256k1_ChaChaPoly_SHA256`. The initiator
+//! knows the static public key of the responder in advance:
+//!
+//! ```text
+//! initiator                          responder
+//! Initiator::act_one       ---->     Responder::process_act_one
+//! Initiator::process_act_two <----   (act two)
+//! (act three)              ---->     Responder::process_act_three
+//! ```
+//!
+//! Both sides end up with a `Transport`, which encrypts and decrypts
+//! length-prefixed messages and rotates its keys every 1000 nonces.
+
+use std::io::{self, Read, Write};
+
+use chacha20poly1305::{
+    aead::{AeadInPlace, KeyInit},
+    ChaCha20Poly1305,
+};
+use hkdf::Hkdf;
+use sha2_10::{Digest, Sha256};
+
+use crate::{Error, PublicKey, SecretKey, SharedSecret};
+
+const PROTOCOL_NAME: &[u8] = b"Noise_XK_secp256k1_ChaChaPoly_SHA256";
+const PROLOGUE: &[u8] = b"lightning";
+const VERSION: u8 = 0;
+const TAG_SIZE: usize = 16;
+const KEY_ROTATION_INTERVAL: u64 = 1000;
+
+/// Size of act one and act two.
+pub const ACT_ONE_SIZE: usize = 50;
+pub const ACT_TWO_SIZE: usize = 50;
+/// Size of act three.
+pub const ACT_THREE_SIZE: usize = 66;
+/// Size of the encrypted length prefix of every message.
+pub const LENGTH_HEADER_SIZE: usize = 2 + TAG_SIZE;
+/// Largest message that fits in a single frame.
+pub const MAX_MESSAGE_SIZE: usize = u16::MAX as usize;
+
+fn sha256(a: &[u8], b: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(a);
+    hasher.update(b);
+    hasher.finalize().into()
+}
+
+fn hkdf(salt: &[u8; 32], ikm: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let mut okm = [0u8; 64];
+    Hkdf::<Sha256>::new(Some(salt), ikm)
+        .expand(&[], &mut okm)
+        .expect("64 bytes is a valid output length for HKDF-SHA256");
+
+    let mut first = [0u8; 32];
+    let mut second = [0u8; 32];
+    first.copy_from_slice(&okm[..32]);
+    second.copy_from_slice(&okm[32..]);
+    unsafe {
+        core::ptr::write_volatile(&mut okm, [0u8; 64]);
+    }
+    (first, second)
+}
+
+fn ecdh(pubkey: &PublicKey, seckey: &SecretKey) -> Result<[u8; 32], Error> {
+    let shared = SharedSecret::<Sha256>::new(pubkey, seckey)?;
+    let mut ret = [0u8; 32];
+    ret.copy_from_slice(shared.as_ref());
+    Ok(ret)
+}
+
+fn nonce(n: u64) -> [u8; 12] {
+    let mut ret = [0u8; 12];
+    ret[4..].copy_from_slice(&n.to_le_bytes());
+    ret
+}
+
+fn encrypt_with_ad(key: &[u8; 32], n: u64, ad: &[u8], plaintext: &[u8], out: &mut [u8]) {
+    debug_assert_eq!(out.len(), plaintext.len() + TAG_SIZE);
+
+    let (ciphertext, tag) = out.split_at_mut(plaintext.len());
+    ciphertext.copy_from_slice(plaintext);
+    let computed = ChaCha20Poly1305::new(key.into())
+        .encrypt_in_place_detached(&nonce(n).into(), ad, ciphertext)
+        .expect("plaintext is within the ChaCha20-Poly1305 size limit");
+    tag.copy_from_slice(&computed);
+}
+
+fn decrypt_with_ad(
+    key: &[u8; 32],
+    n: u64,
+    ad: &[u8],
+    ciphertext: &[u8],
+    out: &mut [u8],
+) -> Result<(), Error> {
+    debug_assert_eq!(out.len() + TAG_SIZE, ciphertext.len());
+
+    let (encrypted, tag) = ciphertext.split_at(out.len());
+    out.copy_from_slice(encrypted);
+    ChaCha20Poly1305::new(key.into())
+        .decrypt_in_place_detached(&nonce(n).into(), ad, out, tag.into())
+        .map_err(|_| Error::InvalidCiphertext)
+}
+
+/// Handshake state shared by both sides.
+struct SymmetricState {
+    ck: [u8; 32],
+    h: [u8; 32],
+    temp_k: [u8; 32],
+}
+
+impl SymmetricState {
+    fn new(responder_static: &PublicKey) -> Self {
+        let h = sha256(PROTOCOL_NAME, &[]);
+        let ck = h;
+        let h = sha256(&h, PROLOGUE);
+        let h = sha256(&h, &responder_static.serialize_compressed());
+        SymmetricState {
+            ck,
+            h,
+            temp_k: [0u8; 32],
+        }
+    }
+
+    fn mix_hash(&mut self, data: &[u8]) {
+        self.h = sha256(&self.h, data);
+    }
+
+    fn mix_key(&mut self, ikm: &[u8; 32]) -> [u8; 32] {
+        let (ck, temp_k) = hkdf(&self.ck, ikm);
+        self.ck = ck;
+        self.temp_k = temp_k;
+        temp_k
+    }
+
+    /// Derive the transport keys. The first key is the one used by the
+    /// initiator to send.
+    fn split(&self) -> ([u8; 32], [u8; 32]) {
+        hkdf(&self.ck, &[])
+    }
+}
+
+/// Parse the `version || public key || tag` layout of acts one and two.
+fn read_ephemeral_act(
+    state: &mut SymmetricState,
+    act: &[u8; ACT_ONE_SIZE],
+    seckey: &SecretKey,
+) -> Result<PublicKey, Error> {
+    if act[0] != VERSION {
+        return Err(Error::InvalidMessage);
+    }
+    let remote_ephemeral = PublicKey::parse_slice(&act[1..34], None)?;
+    state.mix_hash(&act[1..34]);
+    let temp_k = state.mix_key(&ecdh(&remote_ephemeral, seckey)?);
+    decrypt_with_ad(&temp_k, 0, &state.h, &act[34..], &mut [])?;
+    state.mix_hash(&act[34..]);
+    Ok(remote_ephemeral)
+}
+
+/// Build the `version || public key || tag` layout of acts one and two.
+fn write_ephemeral_act(
+    state: &mut SymmetricState,
+    ephemeral: &SecretKey,
+    remote: &PublicKey,
+) -> Result<[u8; ACT_ONE_SIZE], Error> {
+    let mut act = [0u8; ACT_ONE_SIZE];
+    act[0] = VERSION;
+    act[1..34].copy_from_slice(&PublicKey::from_secret_key(ephemeral).serialize_compressed());
+    state.mix_hash(&act[1..34]);
+    let temp_k = state.mix_key(&ecdh(remote, ephemeral)?);
+    encrypt_with_ad(&temp_k, 0, &state.h, &[], &mut act[34..]);
+    state.mix_hash(&act[34..]);
+    Ok(act)
+}
+
+/// The side of the handshake that opens the connection.
+pub struct Initiator {
+    state: SymmetricState,
+    local_static: SecretKey,
+    remote_static: PublicKey,
+    ephemeral: SecretKey,
+}
+
+impl Initiator {
+    /// Start a handshake with the node whose static key is `remote_static`.
+    /// `ephemeral` must be freshly generated for every connection.
+    pub fn new(local_static: SecretKey, remote_static: PublicKey, ephemeral: SecretKey) -> Self {
+        Initiator {
+            state: SymmetricState::new(&remote_static),
+            local_static,
+            remote_static,
+            ephemeral,
+        }
+    }
+
+    pub fn act_one(&mut self) -> Result<[u8; ACT_ONE_SIZE], Error> {
+        write_ephemeral_act(&mut self.state, &self.ephemeral, &self.remote_static)
+    }
+
+    /// Process act two, and produce act three along with the transport.
+    pub fn process_act_two(
+        mut self,
+        act: &[u8; ACT_TWO_SIZE],
+    ) -> Result<([u8; ACT_THREE_SIZE], Transport), Error> {
+        let remote_ephemeral = read_ephemeral_act(&mut self.state, act, &self.ephemeral)?;
+        let temp_k2 = self.state.temp_k;
+
+        let mut ret = [0u8; ACT_THREE_SIZE];
+        ret[0] = VERSION;
+        encrypt_with_ad(
+            &temp_k2,
+            1,
+            &self.state.h,
+            &PublicKey::from_secret_key(&self.local_static).serialize_compressed(),
+            &mut ret[1..50],
+        );
+        self.state.mix_hash(&ret[1..50]);
+        let temp_k3 = self
+            .state
+            .mix_key(&ecdh(&remote_ephemeral, &self.local_static)?);
+        encrypt_with_ad(&temp_k3, 0, &self.state.h, &[], &mut ret[50..]);
+
+        let (sk, rk) = self.state.split();
+        Ok((ret, Transport::new(self.state.ck, sk, rk)))
+    }
+}
+
+/// The side of the handshake that accepts the connection.
+pub struct Responder {
+    state: SymmetricState,
+    local_static: SecretKey,
+    ephemeral: SecretKey,
+    remote_ephemeral: Option<PublicKey>,
+}
+
+impl Responder {
+    /// Wait for a handshake. `ephemeral` must be freshly generated for
+    /// every connection.
+    pub fn new(local_static: SecretKey, ephemeral: SecretKey) -> Self {
+        Responder {
+            state: SymmetricState::new(&PublicKey::from_secret_key(&local_static)),
+            local_static,
+            ephemeral,
+            remote_ephemeral: None,
+        }
+    }
+
+    /// Process act one, and produce act two.
+    pub fn process_act_one(
+        &mut self,
+        act: &[u8; ACT_ONE_SIZE],
+    ) -> Result<[u8; ACT_TWO_SIZE], Error> {
+        let remote_ephemeral = read_ephemeral_act(&mut self.state, act, &self.local_static)?;
+        self.remote_ephemeral = Some(remote_ephemeral);
+        write_ephemeral_act(&mut self.state, &self.ephemeral, &remote_ephemeral)
+    }
+
+    /// Process act three. Returns the static public key of the initiator
+    /// along with the transport.
+    pub fn process_act_three(
+        mut self,
+        act: &[u8; ACT_THREE_SIZE],
+    ) -> Result<(PublicKey, Transport), Error> {
+        if self.remote_ephemeral.is_none() {
+            return Err(Error::InvalidMessage);
+        }
+        if act[0] != VERSION {
+            return Err(Error::InvalidMessage);
+        }
+        let temp_k2 = self.state.temp_k;
+
+        let mut remote_static = [0u8; 33];
+        decrypt_with_ad(&temp_k2, 1, &self.state.h, &act[1..50], &mut remote_static)?;
+        let remote_static = PublicKey::parse_compressed(&remote_static)?;
+        self.state.mix_hash(&act[1..50]);
+        let temp_k3 = self.state.mix_key(&ecdh(&remote_static, &self.ephemeral)?);
+        decrypt_with_ad(&temp_k3, 0, &self.state.h, &act[50..], &mut [])?;
+
+        let (rk, sk) = self.state.split();
+        Ok((remote_static, Transport::new(self.state.ck, sk, rk)))
+    }
+}
+
+/// One direction of the encrypted stream.
+struct CipherState {
+    ck: [u8; 32],
+    k: [u8; 32],
+    n: u64,
+}
+
+impl CipherState {
+    fn advance(&mut self) {
+        self.n += 1;
+        if self.n == KEY_ROTATION_INTERVAL {
+            let (ck, k) = hkdf(&self.ck, &self.k);
+            self.ck = ck;
+            self.k = k;
+            self.n = 0;
+        }
+    }
+
+    fn encrypt(&mut self, plaintext: &[u8], out: &mut [u8]) {
+        encrypt_with_ad(&self.k, self.n, &[], plaintext, out);
+        self.advance();
+    }
+
+    fn decrypt(&mut self, ciphertext: &[u8], out: &mut [u8]) -> Result<(), Error> {
+        decrypt_with_ad(&self.k, self.n, &[], ciphertext, out)?;
+        self.advance();
+        Ok(())
+    }
+}
+
+/// Encrypted message stream established by the handshake.
+pub struct Transport {
+    sending: CipherState,
+    receiving: CipherState,
+}
+
+impl Transport {
+    fn new(ck: [u8; 32], sk: [u8; 32], rk: [u8; 32]) -> Self {
+        Transport {
+            sending: CipherState { ck, k: sk, n: 0 },
+            receiving: CipherState { ck, k: rk, n: 0 },
+        }
+    }
+
+    /// Encrypt a message, prefixed with its encrypted length.
+    pub fn encrypt(&mut self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        if message.len() > MAX_MESSAGE_SIZE {
+            return Err(Error::InvalidInputLength);
+        }
+
+        let mut ret = vec![0u8; LENGTH_HEADER_SIZE + message.len() + TAG_SIZE];
+        let (header, body) = ret.split_at_mut(LENGTH_HEADER_SIZE);
+        self.sending
+            .encrypt(&(message.len() as u16).to_be_bytes(), header);
+        self.sending.encrypt(message, body);
+        Ok(ret)
+    }
+
+    /// Decrypt the length prefix of the next message. Returns the number
+    /// of bytes to read for its body, tag included.
+    pub fn decrypt_length(&mut self, header: &[u8; LENGTH_HEADER_SIZE]) -> Result<usize, Error> {
+        let mut len = [0u8; 2];
+        self.receiving.decrypt(header, &mut len)?;
+        Ok(u16::from_be_bytes(len) as usize + TAG_SIZE)
+    }
+
+    /// Decrypt the body of a message, whose length was given by
+    /// `decrypt_length`.
+    pub fn decrypt_body(&mut self, body: &[u8]) -> Result<Vec<u8>, Error> {
+        if body.len() < TAG_SIZE {
+            return Err(Error::InvalidInputLength);
+        }
+
+        let mut ret = vec![0u8; body.len() - TAG_SIZE];
+        self.receiving.decrypt(body, &mut ret)?;
+        Ok(ret)
+    }
+
+    /// Encrypt a message and write it to `writer`.
+    pub fn write_message<W: Write>(&mut self, writer: &mut W, message: &[u8]) -> io::Result<()> {
+        let encrypted = self.encrypt(message).map_err(invalid_data)?;
+        writer.write_all(&encrypted)
+    }
+
+    /// Read the next message from `reader` and decrypt it.
+    pub fn read_message<R: Read>(&mut self, reader: &mut R) -> io::Result<Vec<u8>> {
+        let mut header = [0u8; LENGTH_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        let len = self.decrypt_length(&header).map_err(invalid_data)?;
+
+        let mut body = vec![0u8; len];
+        reader.read_exact(&mut body)?;
+        self.decrypt_body(&body).map_err(invalid_data)
+    }
+}
+
+fn invalid_data(err: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
@@ -0,0 +1,215 @@
+//! ECIES encryption to secp256k1 public keys.
+//!
+//! The wire format is the one used by eciespy and eciesjs:
+//!
+//! ```text
+//! ephemeral public key || nonce || tag (16 bytes) || encrypted payload
+//! ```
+//!
+//! The ephemeral public key is 65 bytes (uncompressed), or 33 bytes when
+//! `Config::compressed_ephemeral_key` is set. The nonce is 16 bytes for
+//! AES-256-GCM and 24 bytes for XChaCha20-Poly1305. The symmetric key is
+//! derived with HKDF-SHA256, without salt or info, from the uncompressed
+//! ephemeral public key followed by the uncompressed shared point.
+
+use aes_gcm::{
+    aead::{consts::U16, AeadInPlace, KeyInit},
+    aes::Aes256,
+    AesGcm,
+};
+use chacha20poly1305::XChaCha20Poly1305;
+use hkdf::Hkdf;
+use rand::Rng;
+use sha2_10::Sha256;
+
+use crate::{
+    curve::{ECMultContext, ECMultGenContext},
+    ecdh_with_hasher_with_context, util, Error, PublicKey, SecretKey,
+};
+
+/// AES-256-GCM with the 16 bytes nonce used by eciespy.
+type Aes256Gcm16 = AesGcm<Aes256, U16>;
+
+const TAG_SIZE: usize = 16;
+
+/// Symmetric cipher used for the payload.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Cipher {
+    /// AES-256-GCM with a 16 bytes nonce.
+    Aes256Gcm,
+    /// XChaCha20-Poly1305 with a 24 bytes nonce.
+    XChaCha20Poly1305,
+}
+
+impl Cipher {
+    fn nonce_size(&self) -> usize {
+        match self {
+            Cipher::Aes256Gcm => 16,
+            Cipher::XChaCha20Poly1305 => 24,
+        }
+    }
+}
+
+/// ECIES parameters. Both sides must agree on them.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Config {
+    pub cipher: Cipher,
+    /// Serialize the ephemeral public key in compressed form.
+    pub compressed_ephemeral_key: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            cipher: Cipher::Aes256Gcm,
+            compressed_ephemeral_key: false,
+        }
+    }
+}
+
+impl Config {
+    fn ephemeral_key_size(&self) -> usize {
+        if self.compressed_ephemeral_key {
+            util::COMPRESSED_PUBLIC_KEY_SIZE
+        } else {
+            util::FULL_PUBLIC_KEY_SIZE
+        }
+    }
+}
+
+/// Derive the symmetric key from the sender's ephemeral public key and the
+/// shared point.
+fn derive_key(
+    ephemeral: &PublicKey,
+    peer: &PublicKey,
+    seckey: &SecretKey,
+    context: &ECMultContext,
+) -> Result<[u8; 32], Error> {
+    let mut master = [0u8; 2 * util::FULL_PUBLIC_KEY_SIZE];
+    master[..util::FULL_PUBLIC_KEY_SIZE].copy_from_slice(&ephemeral.serialize());
+    ecdh_with_hasher_with_context(peer, seckey, context, |x, y| {
+        master[util::FULL_PUBLIC_KEY_SIZE] = util::TAG_PUBKEY_FULL;
+        master[util::FULL_PUBLIC_KEY_SIZE + 1..util::FULL_PUBLIC_KEY_SIZE + 33].copy_from_slice(x);
+        master[util::FULL_PUBLIC_KEY_SIZE + 33..].copy_from_slice(y);
+    })?;
+
+    let mut key = [0u8; 32];
+    let expanded = Hkdf::<Sha256>::new(None, &master).expand(&[], &mut key);
+    unsafe {
+        core::ptr::write_volatile(&mut master, [0u8; 2 * util::FULL_PUBLIC_KEY_SIZE]);
+    }
+    expanded.map_err(|_| Error::InvalidInputLength)?;
+
+    Ok(key)
+}
+
+/// Encrypt `plaintext` to `pubkey`, using the given contexts.
+pub fn encrypt_with_context<R: Rng>(
+    rng: &mut R,
+    pubkey: &PublicKey,
+    plaintext: &[u8],
+    config: &Config,
+    gen_context: &ECMultGenContext,
+    context: &ECMultContext,
+) -> Result<Vec<u8>, Error> {
+    let mut ephemeral_seckey = SecretKey::random(rng);
+    let ephemeral_pubkey = PublicKey::from_secret_key_with_context(&ephemeral_seckey, gen_context);
+    let mut key = derive_key(&ephemeral_pubkey, pubkey, &ephemeral_seckey, context)?;
+
+    let nonce_size = config.cipher.nonce_size();
+    let mut ret =
+        Vec::with_capacity(config.ephemeral_key_size() + nonce_size + TAG_SIZE + plaintext.len());
+    if config.compressed_ephemeral_key {
+        ret.extend_from_slice(&ephemeral_pubkey.serialize_compressed());
+    } else {
+        ret.extend_from_slice(&ephemeral_pubkey.serialize());
+    }
+
+    let mut nonce = [0u8; 24];
+    rng.fill_bytes(&mut nonce[..nonce_size]);
+    let nonce = &nonce[..nonce_size];
+
+    let mut payload = plaintext.to_vec();
+    let tag = match config.cipher {
+        Cipher::Aes256Gcm => Aes256Gcm16::new(&key.into())
+            .encrypt_in_place_detached(nonce.into(), &[], &mut payload)
+            .map_err(|_| Error::InvalidInputLength)?,
+        Cipher::XChaCha20Poly1305 => XChaCha20Poly1305::new(&key.into())
+            .encrypt_in_place_detached(nonce.into(), &[], &mut payload)
+            .map_err(|_| Error::InvalidInputLength)?,
+    };
+    unsafe {
+        core::ptr::write_volatile(&mut key, [0u8; 32]);
+    }
+    ephemeral_seckey.clear();
+
+    ret.extend_from_slice(nonce);
+    ret.extend_from_slice(&tag);
+    ret.extend_from_slice(&payload);
+    Ok(ret)
+}
+
+/// Decrypt a message encrypted to the public key of `seckey`, using the
+/// given context.
+pub fn decrypt_with_context(
+    seckey: &SecretKey,
+    ciphertext: &[u8],
+    config: &Config,
+    context: &ECMultContext,
+) -> Result<Vec<u8>, Error> {
+    let key_size = config.ephemeral_key_size();
+    let nonce_size = config.cipher.nonce_size();
+    if ciphertext.len() < key_size + nonce_size + TAG_SIZE {
+        return Err(Error::InvalidInputLength);
+    }
+
+    let (ephemeral_pubkey, rest) = ciphertext.split_at(key_size);
+    let (nonce, rest) = rest.split_at(nonce_size);
+    let (tag, encrypted) = rest.split_at(TAG_SIZE);
+
+    let ephemeral_pubkey = PublicKey::parse_slice(ephemeral_pubkey, None)?;
+    let mut key = derive_key(&ephemeral_pubkey, &ephemeral_pubkey, seckey, context)?;
+
+    let mut payload = encrypted.to_vec();
+    let decrypted =
+        match config.cipher {
+            Cipher::Aes256Gcm => Aes256Gcm16::new(&key.into()).decrypt_in_place_detached(
+                nonce.into(),
+                &[],
+                &mut payload,
+                tag.into(),
+            ),
+            Cipher::XChaCha20Poly1305 => XChaCha20Poly1305::new(&key.into())
+                .decrypt_in_place_detached(nonce.into(), &[], &mut payload, tag.into()),
+        };
+    unsafe {
+        core::ptr::write_volatile(&mut key, [0u8; 32]);
+    }
+    decrypted.map_err(|_| Error::InvalidCiphertext)?;
+
+    Ok(payload)
+}
+
+#[cfg(any(feature = "static-context", feature = "lazy-static-context"))]
+/// Encrypt `plaintext` to `pubkey`.
+pub fn encrypt<R: Rng>(
+    rng: &mut R,
+    pubkey: &PublicKey,
+    plaintext: &[u8],
+    config: &Config,
+) -> Result<Vec<u8>, Error> {
+    encrypt_with_context(
+        rng,
+        pubkey,
+        plaintext,
+        config,
+        &crate::ECMULT_GEN_CONTEXT,
+        &crate::ECMULT_CONTEXT,
+    )
+}
+
+#[cfg(any(feature = "static-context", feature = "lazy-static-context"))]
+/// Decrypt a message encrypted to the public key of `seckey`.
+pub fn decrypt(seckey: &SecretKey, ciphertext: &[u8], config: &Config) -> Result<Vec<u8>, Error> {
+    decrypt_with_context(seckey, ciphertext, config, &crate::ECMULT_CONTEXT)
+}
@@ -37,6 +37,15 @@ use crate::{
     util::{Decoder, SignatureArray},
 };
 
+#[cfg(feature = "bolt8")]
+pub mod bolt8;
+#[cfg(feature = "ecies")]
+pub mod ecies;
+#[cfg(feature = "std")]
+pub mod policy;
+#[cfg(feature = "std")]
+pub mod serde_helpers;
+
 #[cfg(feature = "lazy-static-context")]
 lazy_static::lazy_static! {
     /// A static ECMult context.
@@ -62,6 +71,11 @@ pub static ECMULT_GEN_CONTEXT: ECMultGenContext =
 /// Public key on a secp256k1 curve.
 pub struct PublicKey(Affine);
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// Public key identified by its x coordinate alone, as used by BIP340 and
+/// Taproot. The y coordinate is implicitly even.
+pub struct XOnlyPublicKey(Affine);
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 /// Secret key (256-bit) on a secp256k1 curve.
 pub struct SecretKey(Scalar);
@@ -77,6 +91,16 @@ pub struct Signature {
 /// Tag used for public key recovery from signatures.
 pub struct RecoveryId(u8);
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// An ECDSA signature with the recovery ID needed to recover its public key.
+pub struct RecoverableSignature {
+    pub signature: Signature,
+    pub recovery_id: RecoveryId,
+    /// Whether the signer used a compressed public key. This is only
+    /// carried by the Bitcoin compact encoding.
+    pub compressed: bool,
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 /// Hashed message input to an ECDSA signature.
 pub struct Message(pub Scalar);
@@ -85,6 +109,7 @@ pub struct Message(pub Scalar);
 /// Shared secret using ECDH.
 pub struct SharedSecret<D: Digest>(GenericArray<u8, D::OutputSize>);
 
+#[cfg(not(feature = "zeroize"))]
 impl<D> Copy for SharedSecret<D>
 where
     D: Copy + Digest,
@@ -119,6 +144,37 @@ impl PublicKey {
         Self::from_secret_key_with_context(seckey, &ECMULT_GEN_CONTEXT)
     }
 
+    #[cfg(feature = "std")]
+    /// Derive the public keys of many secret keys, using the given context.
+    /// The keys are normalized to affine coordinates with a single shared
+    /// inversion, instead of one per key. That inversion is not constant
+    /// time.
+    pub fn from_secret_keys_with_context(
+        seckeys: &[SecretKey],
+        context: &ECMultGenContext,
+    ) -> Vec<PublicKey> {
+        let pjs: Vec<Jacobian> = seckeys
+            .iter()
+            .map(|seckey| {
+                let mut pj = Jacobian::default();
+                context.ecmult_gen(&mut pj, &seckey.0);
+                pj
+            })
+            .collect();
+        let mut ps = vec![Affine::default(); pjs.len()];
+        util::set_all_gej_var(&mut ps, &pjs);
+
+        ps.into_iter().map(PublicKey).collect()
+    }
+
+    #[cfg(all(
+        feature = "std",
+        any(feature = "static-context", feature = "lazy-static-context")
+    ))]
+    pub fn from_secret_keys(seckeys: &[SecretKey]) -> Vec<PublicKey> {
+        Self::from_secret_keys_with_context(seckeys, &ECMULT_GEN_CONTEXT)
+    }
+
     pub fn parse_slice(p: &[u8], format: Option<PublicKeyFormat>) -> Result<PublicKey, Error> {
         let format = match (p.len(), format) {
             (util::FULL_PUBLIC_KEY_SIZE, None)
@@ -161,30 +217,38 @@ impl PublicKey {
             || p[0] == TAG_PUBKEY_HYBRID_EVEN
             || p[0] == TAG_PUBKEY_HYBRID_ODD)
         {
-            return Err(Error::InvalidPublicKey);
+            return Err(Error::InvalidPublicKeyEncoding(PublicKeyError::InvalidTag(
+                p[0],
+            )));
         }
         let mut x = Field::default();
         let mut y = Field::default();
         if !x.set_b32(array_ref!(p, 1, 32)) {
-            return Err(Error::InvalidPublicKey);
+            return Err(Error::InvalidPublicKeyEncoding(
+                PublicKeyError::CoordinateOverflow,
+            ));
         }
         if !y.set_b32(array_ref!(p, 33, 32)) {
-            return Err(Error::InvalidPublicKey);
+            return Err(Error::InvalidPublicKeyEncoding(
+                PublicKeyError::CoordinateOverflow,
+            ));
         }
         let mut elem = Affine::default();
         elem.set_xy(&x, &y);
         if (p[0] == TAG_PUBKEY_HYBRID_EVEN || p[0] == TAG_PUBKEY_HYBRID_ODD)
             && (y.is_odd() != (p[0] == TAG_PUBKEY_HYBRID_ODD))
         {
-            return Err(Error::InvalidPublicKey);
+            return Err(Error::InvalidPublicKeyEncoding(
+                PublicKeyError::HybridParityMismatch,
+            ));
         }
         if elem.is_infinity() {
-            return Err(Error::InvalidPublicKey);
+            return Err(Error::InvalidPublicKeyEncoding(PublicKeyError::NotOnCurve));
         }
         if elem.is_valid_var() {
             Ok(PublicKey(elem))
         } else {
-            Err(Error::InvalidPublicKey)
+            Err(Error::InvalidPublicKeyEncoding(PublicKeyError::NotOnCurve))
         }
     }
 
@@ -194,21 +258,25 @@ impl PublicKey {
         use util::{TAG_PUBKEY_EVEN, TAG_PUBKEY_ODD};
 
         if !(p[0] == TAG_PUBKEY_EVEN || p[0] == TAG_PUBKEY_ODD) {
-            return Err(Error::InvalidPublicKey);
+            return Err(Error::InvalidPublicKeyEncoding(PublicKeyError::InvalidTag(
+                p[0],
+            )));
         }
         let mut x = Field::default();
         if !x.set_b32(array_ref!(p, 1, 32)) {
-            return Err(Error::InvalidPublicKey);
+            return Err(Error::InvalidPublicKeyEncoding(
+                PublicKeyError::CoordinateOverflow,
+            ));
         }
         let mut elem = Affine::default();
         elem.set_xo_var(&x, p[0] == TAG_PUBKEY_ODD);
         if elem.is_infinity() {
-            return Err(Error::InvalidPublicKey);
+            return Err(Error::InvalidPublicKeyEncoding(PublicKeyError::NotOnCurve));
         }
         if elem.is_valid_var() {
             Ok(PublicKey(elem))
         } else {
-            Err(Error::InvalidPublicKey)
+            Err(Error::InvalidPublicKeyEncoding(PublicKeyError::NotOnCurve))
         }
     }
 
@@ -310,6 +378,102 @@ impl PublicKey {
         let q = Affine::from_gej(&qj);
         Ok(PublicKey(q))
     }
+
+    /// Drop the parity of the y coordinate. Also returns whether it was odd.
+    pub fn x_only(&self) -> (XOnlyPublicKey, bool) {
+        let mut elem = self.0;
+        elem.y.normalize_var();
+        let odd = elem.y.is_odd();
+        if odd {
+            elem = elem.neg();
+        }
+        (XOnlyPublicKey(elem), odd)
+    }
+}
+
+/// Public keys are ordered by their compressed serialization.
+impl Ord for PublicKey {
+    fn cmp(&self, other: &PublicKey) -> core::cmp::Ordering {
+        self.serialize_compressed()
+            .cmp(&other.serialize_compressed())
+    }
+}
+
+impl PartialOrd for PublicKey {
+    fn partial_cmp(&self, other: &PublicKey) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Sort the public keys of a multisig script as described in BIP67.
+pub fn sort_multisig_keys(keys: &mut [PublicKey]) {
+    keys.sort_unstable();
+}
+
+/// Sort the public keys of a MuSig2 aggregate, as done by `KeySort` in
+/// BIP327. This is the same order as BIP67.
+pub fn sort_musig_keys(keys: &mut [PublicKey]) {
+    sort_multisig_keys(keys);
+}
+
+impl XOnlyPublicKey {
+    pub fn parse(p: &[u8; 32]) -> Result<XOnlyPublicKey, Error> {
+        let mut x = Field::default();
+        if !x.set_b32(p) {
+            return Err(Error::InvalidPublicKeyEncoding(
+                PublicKeyError::CoordinateOverflow,
+            ));
+        }
+        let mut elem = Affine::default();
+        if elem.set_xo_var(&x, false) && elem.is_valid_var() {
+            Ok(XOnlyPublicKey(elem))
+        } else {
+            Err(Error::InvalidPublicKeyEncoding(PublicKeyError::NotOnCurve))
+        }
+    }
+
+    pub fn parse_slice(p: &[u8]) -> Result<XOnlyPublicKey, Error> {
+        if p.len() != 32 {
+            return Err(Error::InvalidInputLength);
+        }
+
+        let mut a = [0; 32];
+        a.copy_from_slice(p);
+        Self::parse(&a)
+    }
+
+    pub fn serialize(&self) -> [u8; 32] {
+        let mut ret = [0u8; 32];
+        let mut x = self.0.x;
+        x.normalize_var();
+        x.fill_b32(&mut ret);
+        ret
+    }
+}
+
+impl From<PublicKey> for XOnlyPublicKey {
+    fn from(pubkey: PublicKey) -> XOnlyPublicKey {
+        pubkey.x_only().0
+    }
+}
+
+impl From<XOnlyPublicKey> for PublicKey {
+    fn from(pubkey: XOnlyPublicKey) -> PublicKey {
+        PublicKey(pubkey.0)
+    }
+}
+
+/// X-only public keys are ordered by their 32 bytes serialization.
+impl Ord for XOnlyPublicKey {
+    fn cmp(&self, other: &XOnlyPublicKey) -> core::cmp::Ordering {
+        self.serialize().cmp(&other.serialize())
+    }
+}
+
+impl PartialOrd for XOnlyPublicKey {
+    fn partial_cmp(&self, other: &XOnlyPublicKey) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl Into<Affine> for PublicKey {
@@ -367,8 +531,7 @@ impl<'de> de::Visitor<'de> for PublicKeyStrVisitor {
             65 => PublicKeyFormat::Full,
             _ => return Err(E::custom(Error::InvalidInputLength)),
         };
-        PublicKey::parse_slice(value, Some(key_format))
-            .map_err(|_e| E::custom(Error::InvalidPublicKey))
+        PublicKey::parse_slice(value, Some(key_format)).map_err(E::custom)
     }
 }
 
@@ -389,7 +552,7 @@ impl<'de> de::Visitor<'de> for PublicKeyBytesVisitor {
     where
         E: de::Error,
     {
-        PublicKey::parse_slice(value, None).map_err(|_e| E::custom(Error::InvalidPublicKey))
+        PublicKey::parse_slice(value, None).map_err(E::custom)
     }
 }
 
@@ -413,7 +576,7 @@ impl SecretKey {
         if !bool::from(elem.set_b32(p)) {
             Self::try_from(elem)
         } else {
-            Err(Error::InvalidSecretKey)
+            Err(Error::InvalidScalar(ScalarError::Overflow))
         }
     }
 
@@ -487,6 +650,15 @@ impl Default for SecretKey {
     }
 }
 
+/// `SecretKey` is `Copy`, so it cannot wipe itself on drop. Wrap it in
+/// `zeroize::Zeroizing` where that is needed.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        self.clear();
+    }
+}
+
 impl Into<Scalar> for SecretKey {
     fn into(self) -> Scalar {
         self.0
@@ -498,7 +670,7 @@ impl TryFrom<Scalar> for SecretKey {
 
     fn try_from(scalar: Scalar) -> Result<Self, Error> {
         if scalar.is_zero() {
-            Err(Error::InvalidSecretKey)
+            Err(Error::InvalidScalar(ScalarError::Zero))
         } else {
             Ok(Self(scalar))
         }
@@ -546,7 +718,7 @@ impl Signature {
         let overflowed_s = s.set_b32(array_ref!(p, 32, 32));
 
         if bool::from(overflowed_r | overflowed_s) {
-            return Err(Error::InvalidSignature);
+            return Err(Error::InvalidScalar(ScalarError::Overflow));
         }
 
         Ok(Signature { r, s })
@@ -586,14 +758,14 @@ impl Signature {
         let rlen = decoder.read_len()?;
 
         if rlen != decoder.remaining_len() {
-            return Err(Error::InvalidSignature);
+            return Err(decoder.error(DerField::SequenceLength));
         }
 
         let r = decoder.read_integer()?;
         let s = decoder.read_integer()?;
 
         if decoder.remaining_len() != 0 {
-            return Err(Error::InvalidSignature);
+            return Err(decoder.error(DerField::TrailingData));
         }
 
         Ok(Signature { r, s })
@@ -612,7 +784,15 @@ impl Signature {
         let r = decoder.read_integer_lax()?;
         let s = decoder.read_integer_lax()?;
 
-        Ok(Signature { r, s })
+        // As in libsecp256k1, an overflow of either value yields the zero
+        // signature, which never verifies.
+        match (r, s) {
+            (Some(r), Some(s)) => Ok(Signature { r, s }),
+            _ => Ok(Signature {
+                r: Scalar::default(),
+                s: Scalar::default(),
+            }),
+        }
     }
 
     /// Normalizes a signature to a "low S" form. In ECDSA, signatures are
@@ -687,6 +867,26 @@ impl Signature {
     }
 }
 
+#[cfg(feature = "std")]
+impl Serialize for Signature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serde_helpers::signature_compact::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        serde_helpers::signature_compact::deserialize(deserializer)
+    }
+}
+
 impl Message {
     pub fn parse(p: &[u8; util::MESSAGE_SIZE]) -> Message {
         let mut m = Scalar::default();
@@ -712,6 +912,26 @@ impl Message {
     }
 }
 
+#[cfg(feature = "std")]
+impl Serialize for Message {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serde_helpers::serialize_bytes(&self.serialize(), serializer)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for Message {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        serde_helpers::deserialize_bytes(deserializer, "a 32 bytes message", Message::parse_slice)
+    }
+}
+
 impl RecoveryId {
     /// Parse recovery ID starting with 0.
     pub fn parse(p: u8) -> Result<RecoveryId, Error> {
@@ -748,6 +968,125 @@ impl Into<i32> for RecoveryId {
     }
 }
 
+#[cfg(feature = "std")]
+impl Serialize for RecoveryId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for RecoveryId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        RecoveryId::parse(u8::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+impl RecoverableSignature {
+    pub fn new(signature: Signature, recovery_id: RecoveryId, compressed: bool) -> Self {
+        RecoverableSignature {
+            signature,
+            recovery_id,
+            compressed,
+        }
+    }
+
+    /// Parse a Bitcoin compact signature, as produced by `signmessage`. The
+    /// header byte is `27 + recovery_id`, plus 4 if the public key is
+    /// compressed, followed by `r` and `s`.
+    pub fn parse_compact(p: &[u8; util::RECOVERABLE_SIGNATURE_SIZE]) -> Result<Self, Error> {
+        if p[0] < 27 || p[0] >= 27 + 8 {
+            return Err(Error::InvalidRecoveryId);
+        }
+        let header = p[0] - 27;
+        let recovery_id = RecoveryId::parse(header & 3)?;
+        let signature = Signature::parse_standard(array_ref!(p, 1, 64))?;
+
+        Ok(Self::new(signature, recovery_id, header & 4 != 0))
+    }
+
+    /// Parse a Bitcoin compact signature slice. See also `parse_compact`.
+    pub fn parse_compact_slice(p: &[u8]) -> Result<Self, Error> {
+        if p.len() != util::RECOVERABLE_SIGNATURE_SIZE {
+            return Err(Error::InvalidInputLength);
+        }
+
+        let mut a = [0; util::RECOVERABLE_SIGNATURE_SIZE];
+        a.copy_from_slice(p);
+        Self::parse_compact(&a)
+    }
+
+    /// Serialize to the Bitcoin compact format. This is the reverse of
+    /// `parse_compact`.
+    pub fn serialize_compact(&self) -> [u8; util::RECOVERABLE_SIGNATURE_SIZE] {
+        let mut ret = [0u8; util::RECOVERABLE_SIGNATURE_SIZE];
+        ret[0] = 27 + self.recovery_id.0 + if self.compressed { 4 } else { 0 };
+        ret[1..].copy_from_slice(&self.signature.serialize());
+        ret
+    }
+
+    /// Parse an Ethereum signature, laid out as `r || s || v`. `v` may be
+    /// either the raw recovery ID or the RPC format starting with 27.
+    /// Ethereum keys are always uncompressed.
+    pub fn parse_ethereum(p: &[u8; util::RECOVERABLE_SIGNATURE_SIZE]) -> Result<Self, Error> {
+        let recovery_id = if p[64] >= 27 {
+            RecoveryId::parse_rpc(p[64])?
+        } else {
+            RecoveryId::parse(p[64])?
+        };
+        let signature = Signature::parse_standard(array_ref!(p, 0, 64))?;
+
+        Ok(Self::new(signature, recovery_id, false))
+    }
+
+    /// Parse an Ethereum signature slice. See also `parse_ethereum`.
+    pub fn parse_ethereum_slice(p: &[u8]) -> Result<Self, Error> {
+        if p.len() != util::RECOVERABLE_SIGNATURE_SIZE {
+            return Err(Error::InvalidInputLength);
+        }
+
+        let mut a = [0; util::RECOVERABLE_SIGNATURE_SIZE];
+        a.copy_from_slice(p);
+        Self::parse_ethereum(&a)
+    }
+
+    /// Serialize to the Ethereum format, with `v` starting at 27.
+    pub fn serialize_ethereum(&self) -> [u8; util::RECOVERABLE_SIGNATURE_SIZE] {
+        let mut ret = [0u8; util::RECOVERABLE_SIGNATURE_SIZE];
+        ret[..64].copy_from_slice(&self.signature.serialize());
+        ret[64] = 27 + self.recovery_id.0;
+        ret
+    }
+
+    /// Recover the public key that produced this signature, using the given
+    /// context. Use `compressed` to pick the serialization of the result.
+    pub fn recover_with_context(
+        &self,
+        message: &Message,
+        context: &ECMultContext,
+    ) -> Result<PublicKey, Error> {
+        recover_with_context(message, &self.signature, &self.recovery_id, context)
+    }
+
+    #[cfg(any(feature = "static-context", feature = "lazy-static-context"))]
+    /// Recover the public key that produced this signature.
+    pub fn recover(&self, message: &Message) -> Result<PublicKey, Error> {
+        self.recover_with_context(message, &ECMULT_CONTEXT)
+    }
+}
+
+impl From<RecoverableSignature> for Signature {
+    fn from(signature: RecoverableSignature) -> Signature {
+        signature.signature
+    }
+}
+
 impl<D: Digest + Default + FixedOutputReset> SharedSecret<D> {
     pub fn new_with_context(
         pubkey: &PublicKey,
@@ -768,12 +1107,55 @@ impl<D: Digest + Default + FixedOutputReset> SharedSecret<D> {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl<D: Digest> zeroize::Zeroize for SharedSecret<D> {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(self.0.as_mut_slice());
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<D: Digest> Drop for SharedSecret<D> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<D: Digest> zeroize::ZeroizeOnDrop for SharedSecret<D> {}
+
 impl<D: Digest> AsRef<[u8]> for SharedSecret<D> {
     fn as_ref(&self) -> &[u8] {
         &self.0.as_ref()
     }
 }
 
+/// Compute an ECDH shared secret with a custom hash function, using the given
+/// context. `hasher` receives the x and y coordinates of the shared point.
+/// For example, returning `*x` yields the raw x-only secret.
+pub fn ecdh_with_hasher_with_context<F, R>(
+    pubkey: &PublicKey,
+    seckey: &SecretKey,
+    context: &ECMultContext,
+    hasher: F,
+) -> Result<R, Error>
+where
+    F: FnOnce(&[u8; 32], &[u8; 32]) -> R,
+{
+    context
+        .ecdh_with_hasher(&pubkey.0, &seckey.0, hasher)
+        .ok_or(Error::InvalidSecretKey)
+}
+
+#[cfg(any(feature = "static-context", feature = "lazy-static-context"))]
+/// Compute an ECDH shared secret with a custom hash function.
+pub fn ecdh_with_hasher<F, R>(pubkey: &PublicKey, seckey: &SecretKey, hasher: F) -> Result<R, Error>
+where
+    F: FnOnce(&[u8; 32], &[u8; 32]) -> R,
+{
+    ecdh_with_hasher_with_context(pubkey, seckey, &ECMULT_CONTEXT, hasher)
+}
+
 /// Check signature is a valid message signed by public key, using the given context.
 pub fn verify_with_context(
     message: &Message,
@@ -819,7 +1201,7 @@ pub fn sign_with_context(
     seckey: &SecretKey,
     context: &ECMultGenContext,
 ) -> (Signature, RecoveryId) {
-    let seckey_b32 = seckey.0.b32();
+    let mut seckey_b32 = seckey.0.b32();
     let message_b32 = message.0.b32();
 
     let mut drbg = HmacDRBG::<Sha256>::new(&seckey_b32, &message_b32, &[]);
@@ -828,8 +1210,11 @@ pub fn sign_with_context(
 
     let result;
     loop {
-        let generated = drbg.generate::<U32>(None);
+        let mut generated = drbg.generate::<U32>(None);
         overflow = bool::from(nonce.set_b32(array_ref!(generated, 0, 32)));
+        unsafe {
+            core::ptr::write_volatile(&mut generated, GenericArray::default());
+        }
 
         if !overflow && !nonce.is_zero() {
             if let Ok(val) = context.sign_raw(&seckey.0, &message.0, &nonce) {
@@ -839,9 +1224,12 @@ pub fn sign_with_context(
         }
     }
 
-    #[allow(unused_assignments)]
-    {
-        nonce = Scalar::default();
+    nonce.clear();
+    unsafe {
+        core::ptr::write_volatile(&mut seckey_b32, [0u8; 32]);
+        // `HmacDRBG` keeps its state private, so overwrite it with an
+        // instance that does not depend on the secret key.
+        core::ptr::write_volatile(&mut drbg, HmacDRBG::<Sha256>::new(&[], &[], &[]));
     }
     let (sigr, sigs, recid) = result;
 
@@ -884,4 +1272,14 @@ mod tests {
         sk.clear();
         assert_eq!(sk.is_zero(), true);
     }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn secret_key_zeroize_is_correct() {
+        use zeroize::Zeroize;
+
+        let mut sk = SecretKey::parse(&[1; 32]).unwrap();
+        sk.zeroize();
+        assert!(sk.is_zero());
+    }
 }
@@ -0,0 +1,308 @@
+//! Signature policy checks.
+//!
+//! `Signature::parse_der` and `Signature::parse_der_lax` only tell whether a
+//! signature can be decoded. Relay policy is usually stricter than that, and
+//! it is useful to know exactly why a signature is considered non-standard.
+//! `SignaturePolicy` runs the strict DER encoding rules of BIP66, the low-S
+//! rule of BIP62 and any number of user-provided hooks, and collects every
+//! violated rule in a `PolicyReport`.
+
+use core::fmt;
+
+use crate::Signature;
+
+/// A policy rule violated by a signature.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Violation {
+    /// The encoding is shorter than the smallest possible DER signature.
+    TooShort,
+    /// The encoding is longer than the largest possible DER signature.
+    TooLong,
+    /// The first byte is not a compound structure tag.
+    SequenceTag,
+    /// The sequence length does not cover exactly the rest of the encoding.
+    SequenceLength,
+    /// The length of `r` runs past the end of the encoding.
+    RLength,
+    /// The length of `s` does not match the rest of the encoding.
+    SLength,
+    /// `r` is not tagged as an integer.
+    RTag,
+    /// `r` has zero length.
+    RZeroLength,
+    /// `r` is negative.
+    RNegative,
+    /// `r` has excessive leading zero bytes.
+    RPadding,
+    /// `s` is not tagged as an integer.
+    STag,
+    /// `s` has zero length.
+    SZeroLength,
+    /// `s` is negative.
+    SNegative,
+    /// `s` has excessive leading zero bytes.
+    SPadding,
+    /// The signature cannot be decoded, even with lax DER rules.
+    Malformed,
+    /// `s` is in the upper half of the curve order.
+    HighS,
+    /// The trailing sighash type is not one of the defined types.
+    UndefinedHashType(u8),
+    /// The signature is empty, so it has no sighash type.
+    MissingHashType,
+    /// A rule added through `SignaturePolicy::with_hook`.
+    Custom(&'static str),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::TooShort => write!(f, "signature too short"),
+            Violation::TooLong => write!(f, "signature too long"),
+            Violation::SequenceTag => write!(f, "missing sequence tag"),
+            Violation::SequenceLength => write!(f, "sequence length mismatch"),
+            Violation::RLength => write!(f, "r length out of bounds"),
+            Violation::SLength => write!(f, "s length mismatch"),
+            Violation::RTag => write!(f, "r is not an integer"),
+            Violation::RZeroLength => write!(f, "r has zero length"),
+            Violation::RNegative => write!(f, "r is negative"),
+            Violation::RPadding => write!(f, "r has excessive padding"),
+            Violation::STag => write!(f, "s is not an integer"),
+            Violation::SZeroLength => write!(f, "s has zero length"),
+            Violation::SNegative => write!(f, "s is negative"),
+            Violation::SPadding => write!(f, "s has excessive padding"),
+            Violation::Malformed => write!(f, "signature cannot be decoded"),
+            Violation::HighS => write!(f, "s is not low"),
+            Violation::UndefinedHashType(t) => write!(f, "undefined sighash type {:#04x}", t),
+            Violation::MissingHashType => write!(f, "missing sighash type"),
+            Violation::Custom(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+/// Every rule a signature violates, in the order they were checked.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct PolicyReport {
+    violations: Vec<Violation>,
+}
+
+impl PolicyReport {
+    /// Whether no rule was violated.
+    pub fn is_standard(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Whether `violation` was reported.
+    pub fn contains(&self, violation: Violation) -> bool {
+        self.violations.contains(&violation)
+    }
+
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// Record a violation. Intended for hooks.
+    pub fn push(&mut self, violation: Violation) {
+        self.violations.push(violation);
+    }
+}
+
+impl fmt::Display for PolicyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.violations.is_empty() {
+            return write!(f, "standard");
+        }
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+/// An extra rule run after the built-in checks. It receives the DER
+/// encoding, without any sighash byte.
+type Hook = Box<dyn Fn(&[u8], &mut PolicyReport) + Send + Sync>;
+
+/// A configurable set of signature rules.
+pub struct SignaturePolicy {
+    /// Enforce strict DER encoding (BIP66).
+    pub strict_der: bool,
+    /// Require `s` to be in the lower half of the curve order (BIP62).
+    pub low_s: bool,
+    /// Require the sighash byte to be one of the defined types. Only used
+    /// by `check_with_hashtype`.
+    pub strict_hashtype: bool,
+    hooks: Vec<Hook>,
+}
+
+impl Default for SignaturePolicy {
+    fn default() -> Self {
+        SignaturePolicy {
+            strict_der: true,
+            low_s: true,
+            strict_hashtype: true,
+            hooks: Vec::new(),
+        }
+    }
+}
+
+impl SignaturePolicy {
+    /// A policy that enforces no rule at all.
+    pub fn permissive() -> Self {
+        SignaturePolicy {
+            strict_der: false,
+            low_s: false,
+            strict_hashtype: false,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Add an extra rule, such as a NULLDUMMY or NULLFAIL style check
+    /// that depends on context this crate does not know about.
+    pub fn with_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&[u8], &mut PolicyReport) + Send + Sync + 'static,
+    {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Check a DER-encoded signature.
+    pub fn check(&self, der: &[u8]) -> PolicyReport {
+        let mut report = PolicyReport::default();
+
+        if self.strict_der {
+            check_strict_der(der, &mut report);
+        }
+
+        let signature = if self.strict_der && report.is_standard() {
+            Signature::parse_der(der)
+        } else {
+            Signature::parse_der_lax(der)
+        };
+        match signature {
+            Ok(signature) => {
+                if self.low_s && signature.s.is_high() {
+                    report.push(Violation::HighS);
+                }
+            }
+            Err(_) => {
+                if report.is_standard() {
+                    report.push(Violation::Malformed);
+                }
+            }
+        }
+
+        for hook in &self.hooks {
+            hook(der, &mut report);
+        }
+
+        report
+    }
+
+    /// Check a signature as found in a script, that is a DER encoding
+    /// followed by a sighash byte. An empty signature goes through the same
+    /// checks and hooks, with an empty DER encoding.
+    pub fn check_with_hashtype(&self, sig: &[u8]) -> PolicyReport {
+        let (hashtype, der) = match sig.split_last() {
+            Some((hashtype, der)) => (Some(*hashtype), der),
+            None => (None, sig),
+        };
+
+        let mut report = self.check(der);
+        if self.strict_hashtype {
+            match hashtype {
+                Some(hashtype) if !matches!(hashtype & !0x80, 0x01..=0x03) => {
+                    report.push(Violation::UndefinedHashType(hashtype));
+                }
+                Some(_) => (),
+                None => report.push(Violation::MissingHashType),
+            }
+        }
+        report
+    }
+}
+
+/// Port of `IsValidSignatureEncoding` from Bitcoin Core, operating on the
+/// DER encoding alone and reporting every failed check instead of stopping
+/// at the first one.
+fn check_strict_der(der: &[u8], report: &mut PolicyReport) {
+    // Format: 0x30 [total-length] 0x02 [R-length] [R] 0x02 [S-length] [S]
+    if der.len() < 8 {
+        report.push(Violation::TooShort);
+    }
+    if der.len() > 72 {
+        report.push(Violation::TooLong);
+    }
+    if der.first() != Some(&0x30) {
+        report.push(Violation::SequenceTag);
+    }
+    if der.get(1).map(|&len| len as usize + 2) != Some(der.len()) {
+        report.push(Violation::SequenceLength);
+    }
+    if der.get(2) != Some(&0x02) {
+        report.push(Violation::RTag);
+    }
+
+    let len_r = match der.get(3) {
+        Some(&len) => len as usize,
+        None => {
+            report.push(Violation::RLength);
+            return;
+        }
+    };
+    let r = match der.get(4..4 + len_r) {
+        Some(r) if 4 + len_r < der.len() => r,
+        _ => {
+            report.push(Violation::RLength);
+            return;
+        }
+    };
+    check_integer(
+        r,
+        report,
+        Violation::RZeroLength,
+        Violation::RNegative,
+        Violation::RPadding,
+    );
+
+    if der[4 + len_r] != 0x02 {
+        report.push(Violation::STag);
+    }
+    let len_s = match der.get(5 + len_r) {
+        Some(&len) => len as usize,
+        None => {
+            report.push(Violation::SLength);
+            return;
+        }
+    };
+    if 6 + len_r + len_s != der.len() {
+        report.push(Violation::SLength);
+        return;
+    }
+    check_integer(
+        &der[6 + len_r..],
+        report,
+        Violation::SZeroLength,
+        Violation::SNegative,
+        Violation::SPadding,
+    );
+}
+
+fn check_integer(
+    value: &[u8],
+    report: &mut PolicyReport,
+    zero_length: Violation,
+    negative: Violation,
+    padding: Violation,
+) {
+    match value {
+        [] => report.push(zero_length),
+        [first, ..] if first & 0x80 != 0 => report.push(negative),
+        [0x00, second, ..] if second & 0x80 == 0 => report.push(padding),
+        _ => {}
+    }
+}
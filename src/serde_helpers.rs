@@ -0,0 +1,145 @@
+//! Serde helpers for signatures, messages and keys.
+//!
+//! Human-readable formats (such as JSON) use lowercase hex strings, while
+//! binary formats (such as bincode) use the raw bytes. `Signature`,
+//! `Message` and `RecoveryId` implement `Serialize`/`Deserialize` directly;
+//! the modules below are meant for `#[serde(with = "...")]` to pick another
+//! signature encoding, or to opt in to serializing a `SecretKey`.
+
+use core::fmt;
+use serde::{de, Deserializer, Serializer};
+
+use crate::Error;
+
+pub(crate) fn serialize_bytes<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&hex::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+pub(crate) fn deserialize_bytes<'de, D, T>(
+    deserializer: D,
+    expecting: &'static str,
+    parse: fn(&[u8]) -> Result<T, Error>,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let visitor = BytesVisitor { expecting, parse };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(visitor)
+    } else {
+        deserializer.deserialize_bytes(visitor)
+    }
+}
+
+struct BytesVisitor<T> {
+    expecting: &'static str,
+    parse: fn(&[u8]) -> Result<T, Error>,
+}
+
+impl<'de, T> de::Visitor<'de> for BytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let value = hex::decode(value).map_err(E::custom)?;
+        self.visit_bytes(&value)
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        (self.parse)(value).map_err(E::custom)
+    }
+}
+
+/// Serialize a `Signature` in its 64-byte compact form. This is the same
+/// representation used by the `Serialize` implementation of `Signature`.
+pub mod signature_compact {
+    use serde::{Deserializer, Serializer};
+
+    use crate::Signature;
+
+    pub fn serialize<S>(signature: &Signature, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_bytes(&signature.serialize(), serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Signature, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_bytes(
+            deserializer,
+            "a 64 bytes compact signature",
+            Signature::parse_standard_slice,
+        )
+    }
+}
+
+/// Serialize a `Signature` in its DER encoding.
+pub mod signature_der {
+    use serde::{Deserializer, Serializer};
+
+    use crate::Signature;
+
+    pub fn serialize<S>(signature: &Signature, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_bytes(signature.serialize_der().as_ref(), serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Signature, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_bytes(
+            deserializer,
+            "a DER-encoded signature",
+            Signature::parse_der,
+        )
+    }
+}
+
+/// Serialize a `SecretKey` as its 32 bytes. `SecretKey` deliberately does
+/// not implement `Serialize`, so that secrets are only written out where a
+/// field explicitly opts in with `#[serde(with = "...")]`.
+pub mod secret_key {
+    use serde::{Deserializer, Serializer};
+
+    use crate::SecretKey;
+
+    pub fn serialize<S>(seckey: &SecretKey, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_bytes(&seckey.serialize(), serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<SecretKey, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_bytes(
+            deserializer,
+            "a 32 bytes secret key",
+            SecretKey::parse_slice,
+        )
+    }
+}
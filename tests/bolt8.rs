@@ -0,0 +1,208 @@
+#![cfg(feature = "bolt8")]
+
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Write},
+};
+
+use lib::{
+    bolt8::{Initiator, Responder, Transport, ACT_ONE_SIZE, ACT_THREE_SIZE, ACT_TWO_SIZE},
+    Error, PublicKey, SecretKey,
+};
+
+/// In-memory byte stream between the two peers.
+#[derive(Default)]
+struct Pipe(VecDeque<u8>);
+
+impl Write for Pipe {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.extend(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Read for Pipe {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+fn seckey(byte: u8) -> SecretKey {
+    SecretKey::parse(&[byte; 32]).unwrap()
+}
+
+fn hex_array<const N: usize>(hex: &str) -> [u8; N] {
+    let mut ret = [0u8; N];
+    ret.copy_from_slice(&hex::decode(hex).unwrap());
+    ret
+}
+
+fn initiator() -> Initiator {
+    let rs = PublicKey::parse_compressed(&hex_array(
+        "028d7500dd4c12685d1f568b4c2b5048e8534b873319f3a8daa612b469132ec7f7",
+    ))
+    .unwrap();
+    Initiator::new(seckey(0x11), rs, seckey(0x12))
+}
+
+fn responder() -> Responder {
+    Responder::new(seckey(0x21), seckey(0x22))
+}
+
+const ACT_ONE: &str = "00036360e856310ce5d294e8be33fc807077dc56ac80d95d9cd4ddbd21325eff73f70df6086551151f58b8afe6c195782c6a";
+const ACT_TWO: &str = "0002466d7fcae563e5cb09a0d1870bb580344804617879a14949cf22285f1bae3f276e2470b93aac583c9ef6eafca3f730ae";
+const ACT_THREE: &str = "00b9e3a702e93e3a9948c2ed6e5fd7590a6e1c3a0344cfc9d5b57357049aa22355361aa02e55a8fc28fef5bd6d71ad0c38228dc68b1c466263b47fdf31e560e139ba";
+
+/// Run the BOLT8 test vector handshake over a pair of pipes.
+fn handshake() -> (Transport, Transport) {
+    let mut to_responder = Pipe::default();
+    let mut to_initiator = Pipe::default();
+
+    let mut initiator = initiator();
+    let mut responder = responder();
+
+    to_responder
+        .write_all(&initiator.act_one().unwrap())
+        .unwrap();
+    let mut act_one = [0u8; ACT_ONE_SIZE];
+    to_responder.read_exact(&mut act_one).unwrap();
+    assert_eq!(hex::encode(act_one), ACT_ONE);
+
+    to_initiator
+        .write_all(&responder.process_act_one(&act_one).unwrap())
+        .unwrap();
+    let mut act_two = [0u8; ACT_TWO_SIZE];
+    to_initiator.read_exact(&mut act_two).unwrap();
+    assert_eq!(hex::encode(act_two), ACT_TWO);
+
+    let (act_three, initiator_transport) = initiator.process_act_two(&act_two).unwrap();
+    to_responder.write_all(&act_three).unwrap();
+    let mut act_three = [0u8; ACT_THREE_SIZE];
+    to_responder.read_exact(&mut act_three).unwrap();
+    assert_eq!(hex::encode(act_three), ACT_THREE);
+
+    let (remote_static, responder_transport) = responder.process_act_three(&act_three).unwrap();
+    assert_eq!(remote_static, PublicKey::from_secret_key(&seckey(0x11)));
+
+    (initiator_transport, responder_transport)
+}
+
+#[test]
+fn test_handshake_vectors() {
+    handshake();
+}
+
+#[test]
+fn test_handshake_failures() {
+    // Bad version.
+    let mut act_one: [u8; ACT_ONE_SIZE] = hex_array(ACT_ONE);
+    act_one[0] = 1;
+    assert_eq!(
+        responder().process_act_one(&act_one),
+        Err(Error::InvalidMessage)
+    );
+
+    // Bad key serialization.
+    let mut act_one: [u8; ACT_ONE_SIZE] = hex_array(ACT_ONE);
+    act_one[1] = 4;
+    assert!(responder().process_act_one(&act_one).is_err());
+
+    // Bad MAC.
+    let mut act_one: [u8; ACT_ONE_SIZE] = hex_array(ACT_ONE);
+    act_one[49] ^= 1;
+    assert_eq!(
+        responder().process_act_one(&act_one),
+        Err(Error::InvalidCiphertext)
+    );
+
+    let mut initiator = initiator();
+    initiator.act_one().unwrap();
+    let mut act_two: [u8; ACT_TWO_SIZE] = hex_array(ACT_TWO);
+    act_two[49] ^= 1;
+    assert_eq!(
+        initiator.process_act_two(&act_two).err(),
+        Some(Error::InvalidCiphertext)
+    );
+
+    let mut responder = responder();
+    responder.process_act_one(&hex_array(ACT_ONE)).unwrap();
+    let mut act_three: [u8; ACT_THREE_SIZE] = hex_array(ACT_THREE);
+    act_three[65] ^= 1;
+    assert_eq!(
+        responder.process_act_three(&act_three).err(),
+        Some(Error::InvalidCiphertext)
+    );
+}
+
+#[test]
+fn test_message_encryption_vectors() {
+    let (mut initiator, mut responder) = handshake();
+    let mut pipe = Pipe::default();
+
+    for i in 0..1002 {
+        let encrypted = initiator.encrypt(b"hello").unwrap();
+        let expected = match i {
+            0 => Some(
+                "cf2b30ddf0cf3f80e7c35a6e6730b59fe802473180f396d88a8fb0db8cbcf25d2f214cf9ea1d95",
+            ),
+            1 => Some(
+                "72887022101f0b6753e0c7de21657d35a4cb2a1f5cde2650528bbc8f837d0f0d7ad833b1a256a1",
+            ),
+            500 => Some(
+                "178cb9d7387190fa34db9c2d50027d21793c9bc2d40b1e14dcf30ebeeeb220f48364f7a4c68bf8",
+            ),
+            501 => Some(
+                "1b186c57d44eb6de4c057c49940d79bb838a145cb528d6e8fd26dbe50a60ca2c104b56b60e45bd",
+            ),
+            1000 => Some(
+                "4a2f3cc3b5e78ddb83dcb426d9863d9d9a723b0337c89dd0b005d89f8d3c05c52b76b29b740f09",
+            ),
+            1001 => Some(
+                "2ecd8c8a5629d0d02ab457a0fdd0f7b90a192cd46be5ecb6ca570bfc5e268338b1a16cf4ef2d36",
+            ),
+            _ => None,
+        };
+        if let Some(expected) = expected {
+            assert_eq!(hex::encode(&encrypted), expected, "message {}", i);
+        }
+
+        pipe.write_all(&encrypted).unwrap();
+        assert_eq!(responder.read_message(&mut pipe).unwrap(), b"hello");
+    }
+}
+
+#[test]
+fn test_message_stream() {
+    let (mut initiator, mut responder) = handshake();
+    let mut to_initiator = Pipe::default();
+    let mut to_responder = Pipe::default();
+
+    let long = vec![0xab; 65535];
+    initiator.write_message(&mut to_responder, &long).unwrap();
+    initiator.write_message(&mut to_responder, b"").unwrap();
+    responder.write_message(&mut to_initiator, b"pong").unwrap();
+
+    assert_eq!(responder.read_message(&mut to_responder).unwrap(), long);
+    assert_eq!(responder.read_message(&mut to_responder).unwrap(), b"");
+    assert_eq!(initiator.read_message(&mut to_initiator).unwrap(), b"pong");
+
+    assert_eq!(
+        initiator.encrypt(&vec![0u8; 65536]),
+        Err(Error::InvalidInputLength)
+    );
+
+    let mut tampered = initiator.encrypt(b"hello").unwrap();
+    tampered[20] ^= 1;
+    to_responder.write_all(&tampered).unwrap();
+    assert_eq!(
+        responder
+            .read_message(&mut to_responder)
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::InvalidData
+    );
+}
@@ -0,0 +1,200 @@
+//! Statistical timing leakage tests, following the dudect methodology
+//! (Reparaz, Balasch and Verbauwhede, "Dude, is my code constant time?").
+//!
+//! Each test times an operation on two classes of inputs, one fixed input
+//! and fresh random ones, interleaved at random. Welch's t-test is applied
+//! to the measurements, both raw and cropped at several percentiles to
+//! discard outliers. A `|t|` above `T_THRESHOLD` means the two timing
+//! distributions differ, which points at a secret-dependent code path.
+//!
+//! Timing is noisy, so these tests are ignored by default. Run them in
+//! release mode on an otherwise idle machine:
+//!
+//! ```text
+//! cargo test --release --test constant_time -- --ignored --test-threads 1
+//! ```
+//!
+//! `DUDECT_MEASUREMENTS` overrides the number of measurements per test.
+
+use std::{env, hint::black_box, time::Instant};
+
+use lib::{ecdh_with_hasher, sign, Message, PublicKey, SecretKey};
+use rand::{rngs::OsRng, Rng};
+
+/// dudect considers `|t| > 10` as definitely not constant time.
+const T_THRESHOLD: f64 = 10.0;
+const DEFAULT_MEASUREMENTS: usize = 20_000;
+const PERCENTILES: usize = 10;
+
+/// Online Welch's t-test over two classes.
+#[derive(Default, Clone, Copy)]
+struct TTest {
+    n: [f64; 2],
+    mean: [f64; 2],
+    m2: [f64; 2],
+}
+
+impl TTest {
+    fn push(&mut self, class: usize, x: f64) {
+        self.n[class] += 1.0;
+        let delta = x - self.mean[class];
+        self.mean[class] += delta / self.n[class];
+        self.m2[class] += delta * (x - self.mean[class]);
+    }
+
+    fn t(&self) -> f64 {
+        if self.n[0] < 2.0 || self.n[1] < 2.0 {
+            return 0.0;
+        }
+        let var0 = self.m2[0] / (self.n[0] - 1.0);
+        let var1 = self.m2[1] / (self.n[1] - 1.0);
+        let den = (var0 / self.n[0] + var1 / self.n[1]).sqrt();
+        if den == 0.0 {
+            0.0
+        } else {
+            (self.mean[0] - self.mean[1]) / den
+        }
+    }
+}
+
+fn measurements() -> usize {
+    env::var("DUDECT_MEASUREMENTS")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(DEFAULT_MEASUREMENTS)
+}
+
+/// Time `op` on inputs of both classes, and return the largest `|t|` over
+/// the raw and cropped measurements.
+fn dudect<I, F, G, O>(fixed: I, mut random: G, mut op: F) -> f64
+where
+    I: Clone,
+    G: FnMut() -> I,
+    F: FnMut(&I) -> O,
+{
+    let count = measurements();
+    let mut rng = OsRng;
+
+    // Generate all inputs upfront so that only `op` is timed.
+    let classes: Vec<usize> = (0..count).map(|_| rng.gen_range(0..2)).collect();
+    let inputs: Vec<I> = classes
+        .iter()
+        .map(|&class| if class == 0 { fixed.clone() } else { random() })
+        .collect();
+
+    let mut timings = Vec::with_capacity(count);
+    for input in &inputs {
+        let start = Instant::now();
+        black_box(op(black_box(input)));
+        timings.push(start.elapsed().as_nanos() as f64);
+    }
+
+    // Crop thresholds, as in dudect: 1 - 0.5^(10 (i + 1) / PERCENTILES).
+    let mut sorted = timings.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let thresholds: Vec<f64> = (0..PERCENTILES)
+        .map(|i| {
+            let p = 1.0 - 0.5f64.powf(10.0 * (i + 1) as f64 / PERCENTILES as f64);
+            sorted[((count as f64 * p) as usize).min(count - 1)]
+        })
+        .collect();
+
+    let mut raw = TTest::default();
+    let mut cropped = vec![TTest::default(); PERCENTILES];
+    // Drop the first measurements to warm up caches.
+    for (&class, &time) in classes.iter().zip(timings.iter()).skip(count / 100) {
+        raw.push(class, time);
+        for (test, &threshold) in cropped.iter_mut().zip(thresholds.iter()) {
+            if time < threshold {
+                test.push(class, time);
+            }
+        }
+    }
+
+    cropped
+        .iter()
+        .map(TTest::t)
+        .chain(core::iter::once(raw.t()))
+        .fold(0.0, |max, t| f64::max(max, t.abs()))
+}
+
+fn assert_constant_time(name: &str, t: f64) {
+    println!("{}: max |t| = {:.2}", name, t);
+    assert!(
+        t < T_THRESHOLD,
+        "{} is likely not constant time: |t| = {:.2}",
+        name,
+        t
+    );
+}
+
+fn low_weight_key() -> SecretKey {
+    let mut a = [0u8; 32];
+    a[31] = 1;
+    SecretKey::parse(&a).unwrap()
+}
+
+#[test]
+#[ignore]
+fn sign_is_constant_time() {
+    let message = Message::parse(&[0x42; 32]);
+    let t = dudect(
+        low_weight_key(),
+        || SecretKey::random(&mut OsRng),
+        |seckey| sign(&message, seckey),
+    );
+    assert_constant_time("sign", t);
+}
+
+#[test]
+#[ignore]
+fn ecdh_is_constant_time() {
+    let pubkey = PublicKey::from_secret_key(&SecretKey::random(&mut OsRng));
+    let t = dudect(
+        low_weight_key(),
+        || SecretKey::random(&mut OsRng),
+        |seckey| ecdh_with_hasher(&pubkey, seckey, |x, _| *x),
+    );
+    assert_constant_time("ecdh", t);
+}
+
+#[test]
+#[ignore]
+fn tweak_add_is_constant_time() {
+    let seckey = SecretKey::random(&mut OsRng);
+    let t = dudect(
+        low_weight_key(),
+        || SecretKey::random(&mut OsRng),
+        |tweak| {
+            let mut key = seckey;
+            key.tweak_add_assign(tweak).map(|_| key)
+        },
+    );
+    assert_constant_time("tweak_add_assign", t);
+}
+
+#[test]
+#[ignore]
+fn tweak_mul_is_constant_time() {
+    let seckey = SecretKey::random(&mut OsRng);
+    let t = dudect(
+        low_weight_key(),
+        || SecretKey::random(&mut OsRng),
+        |tweak| {
+            let mut key = seckey;
+            key.tweak_mul_assign(tweak).map(|_| key)
+        },
+    );
+    assert_constant_time("tweak_mul_assign", t);
+}
+
+#[test]
+#[ignore]
+fn public_key_derivation_is_constant_time() {
+    let t = dudect(
+        low_weight_key(),
+        || SecretKey::random(&mut OsRng),
+        PublicKey::from_secret_key,
+    );
+    assert_constant_time("from_secret_key", t);
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "ecies")]
+
+use lib::{
+    ecies::{decrypt, encrypt, Cipher, Config},
+    Error, PublicKey, SecretKey,
+};
+use rand::rngs::OsRng;
+
+fn seckey(n: u8) -> SecretKey {
+    let mut a = [0u8; 32];
+    a[31] = n;
+    SecretKey::parse(&a).unwrap()
+}
+
+/// Ciphertexts of "hello world" to the receiver key sha256("receiver"),
+/// with the ephemeral key sha256("ephemeral") and the nonce 00 01 02 ...
+///
+/// eciespy and eciesjs could not be installed where these were made, so they
+/// come from a separate Python implementation of the eciespy format instead
+/// of this crate. It uses the `cryptography` package: OpenSSL for the
+/// secp256k1 points, HKDF-SHA256, AES-GCM and ChaCha20-Poly1305, with
+/// HChaCha20 written out for XChaCha20.
+const KNOWN_CIPHERTEXTS: [(Cipher, bool, &str); 4] = [
+    (
+        Cipher::Aes256Gcm,
+        false,
+        "049c7a3a75b43dfa0c28c911a4ff1ff157a176116c4e4d00ca15fead57b4806c038cf0af9d9cfaa6fb03b8fa18f10b3281d8e01eb346447874072321ec98fd35ef\
+         000102030405060708090a0b0c0d0e0f\
+         4cab467c50db31eef48eb5fe5cc8874c\
+         e6822ea663a068de85f764",
+    ),
+    (
+        Cipher::Aes256Gcm,
+        true,
+        "039c7a3a75b43dfa0c28c911a4ff1ff157a176116c4e4d00ca15fead57b4806c03\
+         000102030405060708090a0b0c0d0e0f\
+         4cab467c50db31eef48eb5fe5cc8874c\
+         e6822ea663a068de85f764",
+    ),
+    (
+        Cipher::XChaCha20Poly1305,
+        false,
+        "049c7a3a75b43dfa0c28c911a4ff1ff157a176116c4e4d00ca15fead57b4806c038cf0af9d9cfaa6fb03b8fa18f10b3281d8e01eb346447874072321ec98fd35ef\
+         000102030405060708090a0b0c0d0e0f1011121314151617\
+         4e6dbaac18572808ded149770df3c4b1\
+         91c5ce71f5a721cc4e7347",
+    ),
+    (
+        Cipher::XChaCha20Poly1305,
+        true,
+        "039c7a3a75b43dfa0c28c911a4ff1ff157a176116c4e4d00ca15fead57b4806c03\
+         000102030405060708090a0b0c0d0e0f1011121314151617\
+         4e6dbaac18572808ded149770df3c4b1\
+         91c5ce71f5a721cc4e7347",
+    ),
+];
+
+#[test]
+fn test_decrypt_known_ciphertext() {
+    let receiver = SecretKey::parse_slice(
+        &hex::decode("81bae876b70513c9decc608eed549977a81afa1c2b6b4080aec256339e792e0f").unwrap(),
+    )
+    .unwrap();
+
+    for (cipher, compressed_ephemeral_key, ciphertext) in KNOWN_CIPHERTEXTS {
+        let config = Config {
+            cipher,
+            compressed_ephemeral_key,
+        };
+        let ciphertext = hex::decode(ciphertext).unwrap();
+
+        let plaintext = decrypt(&receiver, &ciphertext, &config).unwrap();
+        assert_eq!(plaintext, b"hello world");
+
+        assert_eq!(
+            decrypt(&seckey(3), &ciphertext, &config),
+            Err(Error::InvalidCiphertext)
+        );
+    }
+}
+
+#[test]
+fn test_encrypt_decrypt() {
+    let mut rng = OsRng;
+    let receiver = SecretKey::random(&mut rng);
+    let pubkey = PublicKey::from_secret_key(&receiver);
+    let message = b"attack at dawn";
+
+    for cipher in [Cipher::Aes256Gcm, Cipher::XChaCha20Poly1305] {
+        for compressed_ephemeral_key in [false, true] {
+            let config = Config {
+                cipher,
+                compressed_ephemeral_key,
+            };
+            let ciphertext = encrypt(&mut rng, &pubkey, message, &config).unwrap();
+            let overhead = match (cipher, compressed_ephemeral_key) {
+                (Cipher::Aes256Gcm, false) => 65 + 16 + 16,
+                (Cipher::Aes256Gcm, true) => 33 + 16 + 16,
+                (Cipher::XChaCha20Poly1305, false) => 65 + 24 + 16,
+                (Cipher::XChaCha20Poly1305, true) => 33 + 24 + 16,
+            };
+            assert_eq!(ciphertext.len(), overhead + message.len());
+            assert_eq!(decrypt(&receiver, &ciphertext, &config).unwrap(), message);
+
+            let mut tampered = ciphertext.clone();
+            *tampered.last_mut().unwrap() ^= 1;
+            assert_eq!(
+                decrypt(&receiver, &tampered, &config),
+                Err(Error::InvalidCiphertext)
+            );
+            assert_eq!(
+                decrypt(&receiver, &ciphertext[..overhead - 1], &config),
+                Err(Error::InvalidInputLength)
+            );
+        }
+    }
+}
@@ -0,0 +1,146 @@
+#![cfg(feature = "std")]
+
+use lib::policy::{SignaturePolicy, Violation};
+use lib::{sign, Message, SecretKey};
+
+fn valid_der() -> Vec<u8> {
+    let seckey = SecretKey::parse(&[1u8; 32]).unwrap();
+    let message = Message::parse(&[2u8; 32]);
+    let (signature, _) = sign(&message, &seckey);
+    signature.serialize_der().as_ref().to_vec()
+}
+
+#[test]
+fn test_standard_signature() {
+    let der = valid_der();
+    let policy = SignaturePolicy::default();
+    assert!(policy.check(&der).is_standard());
+
+    let mut sig = der.clone();
+    sig.push(0x01);
+    assert!(policy.check_with_hashtype(&sig).is_standard());
+}
+
+#[test]
+fn test_empty_signature() {
+    let report = SignaturePolicy::default().check_with_hashtype(&[]);
+    assert!(report.contains(Violation::TooShort));
+    assert!(report.contains(Violation::MissingHashType));
+    assert_eq!(
+        report.violations().last(),
+        Some(&Violation::MissingHashType)
+    );
+
+    // Without strict DER, the empty encoding still fails to decode.
+    assert_eq!(
+        SignaturePolicy::permissive()
+            .check_with_hashtype(&[])
+            .violations(),
+        &[Violation::Malformed]
+    );
+
+    // Hooks see the empty encoding.
+    let policy = SignaturePolicy::permissive().with_hook(|der, report| {
+        if der.is_empty() {
+            report.push(Violation::Custom("empty"));
+        }
+    });
+    assert_eq!(
+        policy.check_with_hashtype(&[]).violations(),
+        &[Violation::Malformed, Violation::Custom("empty")]
+    );
+}
+
+#[test]
+fn test_high_s() {
+    let seckey = SecretKey::parse(&[1u8; 32]).unwrap();
+    let message = Message::parse(&[2u8; 32]);
+    let (mut signature, _) = sign(&message, &seckey);
+    signature.s = -signature.s;
+    let der = signature.serialize_der();
+
+    let report = SignaturePolicy::default().check(der.as_ref());
+    assert_eq!(report.violations(), &[Violation::HighS]);
+
+    let mut policy = SignaturePolicy::default();
+    policy.low_s = false;
+    assert!(policy.check(der.as_ref()).is_standard());
+}
+
+#[test]
+fn test_reports_every_violation() {
+    // Negative and padded r, wrong s tag, and an undefined sighash type.
+    let sig = [
+        0x30, 0x08, 0x02, 0x02, 0x00, 0x01, 0x03, 0x02, 0x80, 0x01, 0x07,
+    ];
+    let report = SignaturePolicy::default().check_with_hashtype(&sig);
+    assert_eq!(
+        report.violations(),
+        &[
+            Violation::RPadding,
+            Violation::STag,
+            Violation::SNegative,
+            Violation::UndefinedHashType(0x07),
+        ]
+    );
+    assert_eq!(
+        report.to_string(),
+        "r has excessive padding, s is not an integer, s is negative, undefined sighash type 0x07"
+    );
+}
+
+#[test]
+fn test_structural_violations() {
+    let policy = SignaturePolicy::default();
+
+    let report = policy.check(&[0x31, 0x06, 0x02, 0x01, 0x01, 0x02]);
+    assert!(report.contains(Violation::TooShort));
+    assert!(report.contains(Violation::SequenceTag));
+    assert!(report.contains(Violation::SequenceLength));
+    assert!(report.contains(Violation::SLength));
+
+    let mut der = valid_der();
+    der.push(0x00);
+    let report = policy.check(&der);
+    assert!(report.contains(Violation::SequenceLength));
+    assert!(report.contains(Violation::SLength));
+}
+
+#[test]
+fn test_lax_policy() {
+    let mut der = valid_der();
+    // Non-minimal length encoding, accepted by lax DER.
+    der.insert(1, 0x81);
+    assert!(!SignaturePolicy::default().check(&der).is_standard());
+    assert!(SignaturePolicy::permissive().check(&der).is_standard());
+
+    assert_eq!(
+        SignaturePolicy::permissive().check(&[0x30]).violations(),
+        &[Violation::Malformed]
+    );
+}
+
+#[test]
+fn test_hooks() {
+    // Stand-in for a rule that needs script context, such as NULLFAIL.
+    let policy = SignaturePolicy::default().with_hook(|der, report| {
+        if !der.is_empty() {
+            report.push(Violation::Custom("signature must be empty"));
+        }
+    });
+
+    assert_eq!(
+        policy.check(&valid_der()).violations(),
+        &[Violation::Custom("signature must be empty")]
+    );
+    assert_eq!(
+        policy.check(&[0x30]).violations(),
+        &[
+            Violation::TooShort,
+            Violation::SequenceLength,
+            Violation::RTag,
+            Violation::RLength,
+            Violation::Custom("signature must be empty"),
+        ]
+    );
+}
@@ -1,6 +1,7 @@
 #![cfg(feature = "std")]
 
-use lib::{PublicKey, SecretKey};
+use lib::{Message, PublicKey, RecoveryId, SecretKey, Signature};
+use serde::{Deserialize, Serialize};
 
 const DEBUG_SECRET_KEY: [u8; 32] = [1u8; 32];
 // Public key for debug secret key
@@ -32,3 +33,89 @@ fn test_public_key_bincode_serde() {
     let pkey2 = bincode::deserialize(&serialized_pkey).unwrap();
     assert_eq!(pkey, pkey2);
 }
+
+const DEBUG_MESSAGE: [u8; 32] = [2u8; 32];
+const DEBUG_SIGNATURE: [u8; 64] = [3u8; 64];
+
+fn debug_signature() -> (Signature, RecoveryId) {
+    (
+        Signature::parse_standard(&DEBUG_SIGNATURE).unwrap(),
+        RecoveryId::parse(1).unwrap(),
+    )
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Signed {
+    #[serde(with = "lib::serde_helpers::secret_key")]
+    seckey: SecretKey,
+    message: Message,
+    signature: Signature,
+    #[serde(with = "lib::serde_helpers::signature_der")]
+    signature_der: Signature,
+    recovery_id: RecoveryId,
+}
+
+fn debug_signed() -> Signed {
+    let (signature, recovery_id) = debug_signature();
+    Signed {
+        seckey: SecretKey::parse(&DEBUG_SECRET_KEY).unwrap(),
+        message: Message::parse(&DEBUG_MESSAGE),
+        signature,
+        signature_der: signature,
+        recovery_id,
+    }
+}
+
+#[test]
+fn test_serialize_signature_is_hex() {
+    let (signature, _) = debug_signature();
+    let serialized = serde_json::to_string(&signature).unwrap();
+    assert_eq!(serialized, format!("\"{}\"", "03".repeat(64)));
+    let deserialized: Signature = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, signature);
+}
+
+#[test]
+fn test_serialize_message_is_hex() {
+    let message = Message::parse(&DEBUG_MESSAGE);
+    let serialized = serde_json::to_string(&message).unwrap();
+    assert_eq!(serialized, format!("\"{}\"", "02".repeat(32)));
+    let deserialized: Message = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, message);
+}
+
+#[test]
+fn test_deserialize_invalid_recovery_id() {
+    assert!(serde_json::from_str::<RecoveryId>("3").is_ok());
+    assert!(serde_json::from_str::<RecoveryId>("4").is_err());
+}
+
+#[test]
+fn test_deserialize_invalid_signature() {
+    // Wrong length for a compact signature.
+    assert!(serde_json::from_str::<Signature>("\"0102\"").is_err());
+    // Not hex at all.
+    assert!(serde_json::from_str::<Signature>("\"zz\"").is_err());
+}
+
+#[test]
+fn test_signed_json_serde() {
+    let signed = debug_signed();
+    let serialized = serde_json::to_string(&signed).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(value["seckey"], "01".repeat(32));
+    assert_eq!(
+        value["signature_der"],
+        hex::encode(signed.signature.serialize_der().as_ref())
+    );
+    let signed2: Signed = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(signed, signed2);
+}
+
+#[test]
+fn test_signed_bincode_serde() {
+    let signed = debug_signed();
+    let serialized: Vec<u8> = bincode::serialize(&signed).unwrap();
+    let signed2: Signed = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(signed, signed2);
+}
@@ -235,6 +235,61 @@ fn test_recover() {
     assert_eq!(sps, gps);
 }
 
+#[test]
+fn test_recoverable_signature() {
+    let secp256k1 = Secp256k1::new();
+
+    let message_arr = [6u8; 32];
+    let (privkey, pubkey) = secp256k1.generate_keypair(&mut thread_rng());
+    let message = SecpMessage::from_slice(&message_arr).unwrap();
+    let (rec_id, signature_a) = secp256k1
+        .sign_recoverable(&message, &privkey)
+        .serialize_compact();
+    let rec_id = rec_id.to_i32() as u8;
+
+    let mut compact = [0u8; 65];
+    compact[0] = 27 + 4 + rec_id;
+    compact[1..].copy_from_slice(&signature_a);
+    let sig = RecoverableSignature::parse_compact(&compact).unwrap();
+    assert!(sig.compressed);
+    assert_eq!(sig.recovery_id.serialize(), rec_id);
+    assert_eq!(sig.serialize_compact(), compact);
+
+    let ctx_message = Message::parse(&message_arr);
+    let ctx_pubkey = sig.recover(&ctx_message).unwrap();
+    assert_eq!(
+        &ctx_pubkey.serialize_compressed()[..],
+        &pubkey.serialize()[..]
+    );
+
+    let eth = sig.serialize_ethereum();
+    assert_eq!(&eth[..64], &signature_a[..]);
+    assert_eq!(eth[64], 27 + rec_id);
+    let eth_sig = RecoverableSignature::parse_ethereum(&eth).unwrap();
+    assert!(!eth_sig.compressed);
+    assert_eq!(eth_sig.recover(&ctx_message).unwrap(), ctx_pubkey);
+
+    let mut eth_raw_v = eth;
+    eth_raw_v[64] = rec_id;
+    assert_eq!(
+        RecoverableSignature::parse_ethereum_slice(&eth_raw_v).unwrap(),
+        eth_sig
+    );
+
+    let plain: Signature = sig.into();
+    assert_eq!(plain.serialize()[..], signature_a[..]);
+
+    compact[0] = 35;
+    assert_eq!(
+        RecoverableSignature::parse_compact(&compact),
+        Err(Error::InvalidRecoveryId)
+    );
+    assert_eq!(
+        RecoverableSignature::parse_compact_slice(&compact[1..]),
+        Err(Error::InvalidInputLength)
+    );
+}
+
 fn from_hex(hex: &str, target: &mut [u8]) -> Result<usize, ()> {
     if hex.len() % 2 == 1 || hex.len() > target.len() * 2 {
         return Err(());
@@ -285,6 +340,53 @@ fn test_signature_der_lax() {
     check_lax_sig!("3044022023ee4e95151b2fbbb08a72f35babe02830d14d54bd7ed1320e4751751d1baa4802206235245254f58fd1be6ff19ca291817da76da65c2f6d81d654b5185dd86b8acf");
 }
 
+#[test]
+fn test_signature_der_overflow() {
+    // Overflowing integers parse as zero, like in libsecp256k1, and the
+    // following fields are still decoded.
+    let mut expected = [0u8; 64];
+    expected[63] = 1;
+    let sig = Signature::parse_der(&hex!("3006020180020101")).unwrap();
+    assert_eq!(sig.serialize(), expected);
+
+    let der = hex!(concat!(
+        "3026020101022101",
+        "0000000000000000000000000000000000000000000000000000000000000000"
+    ));
+    let mut expected = [0u8; 64];
+    expected[31] = 1;
+    assert_eq!(Signature::parse_der(&der).unwrap().serialize(), expected);
+    // The lax parser zeroes the whole signature instead.
+    assert_eq!(
+        Signature::parse_der_lax(&der).unwrap().serialize(),
+        [0u8; 64]
+    );
+
+    let sig = Signature::parse_der(&hex!("3006020101020100")).unwrap();
+    assert_eq!(sig.serialize(), expected);
+
+    // An overflowing `r` is skipped, so `s` is still read from after it.
+    let der = hex!(concat!(
+        "3026022101",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "020101"
+    ));
+    let mut expected = [0u8; 64];
+    expected[63] = 1;
+    assert_eq!(Signature::parse_der(&der).unwrap().serialize(), expected);
+    assert_eq!(
+        Signature::parse_der_lax(&der).unwrap().serialize(),
+        [0u8; 64]
+    );
+    // Neither parser accepts a sequence that ends after the overflowing `r`.
+    let der = hex!(concat!(
+        "3023022101",
+        "0000000000000000000000000000000000000000000000000000000000000000"
+    ));
+    assert!(Signature::parse_der(&der).is_err());
+    assert!(Signature::parse_der_lax(&der).is_err());
+}
+
 #[test]
 fn test_low_s() {
     // nb this is a transaction on testnet
@@ -442,3 +544,255 @@ fn test_pubkey_equality() {
         assert_eq!(public3, public);
     }
 }
+
+#[test]
+fn test_set_all_gej_var() {
+    use lib::{
+        curve::{Affine, Jacobian},
+        util::{set_all_gej_var, JACOBIAN_INFINITY},
+    };
+
+    let mut points = Vec::new();
+    for i in 0..8 {
+        if i % 3 == 0 {
+            points.push(JACOBIAN_INFINITY);
+        }
+        let secret = SecretKey::random(&mut rand::rngs::OsRng);
+        let mut pj = Jacobian::default();
+        ECMULT_GEN_CONTEXT.ecmult_gen(&mut pj, &secret.into());
+        points.push(pj);
+    }
+
+    let mut batch = vec![Affine::default(); points.len()];
+    set_all_gej_var(&mut batch, &points);
+
+    for (pj, p) in points.iter().zip(batch.iter_mut()) {
+        let expected = Affine::from_gej(pj);
+        assert_eq!(p.is_infinity(), expected.is_infinity());
+        if !p.is_infinity() {
+            p.x.normalize();
+            p.y.normalize();
+            assert_eq!(p.x, expected.x);
+            assert_eq!(p.y, expected.y);
+        }
+    }
+}
+
+#[test]
+fn test_from_secret_keys() {
+    let seckeys: Vec<SecretKey> = (0..100)
+        .map(|_| SecretKey::random(&mut rand::rngs::OsRng))
+        .collect();
+    let pubkeys = PublicKey::from_secret_keys(&seckeys);
+
+    assert_eq!(pubkeys.len(), seckeys.len());
+    for (seckey, pubkey) in seckeys.iter().zip(pubkeys.iter()) {
+        assert_eq!(*pubkey, PublicKey::from_secret_key(seckey));
+        assert_eq!(
+            pubkey.serialize(),
+            PublicKey::from_secret_key(seckey).serialize()
+        );
+    }
+    assert!(PublicKey::from_secret_keys(&[]).is_empty());
+}
+
+#[test]
+fn test_inv_all_var() {
+    use lib::{curve::Field, util::inv_all_var};
+
+    let fields: Vec<Field> = (1..10).map(Field::from_int).collect();
+    let mut inverses = vec![Field::default(); fields.len()];
+    inv_all_var(&mut inverses, &fields);
+
+    for (f, inv) in fields.iter().zip(inverses.iter()) {
+        assert_eq!(*inv, f.inv());
+    }
+}
+
+#[test]
+fn test_static_contexts_match_generated() {
+    use lib::curve::{ECMultContext, ECMultGenContext};
+
+    assert_eq!(
+        &ECMultContext::new_boxed().inspect_raw()[..],
+        &ECMULT_CONTEXT.inspect_raw()[..]
+    );
+    assert_eq!(
+        &ECMultGenContext::new_boxed().inspect_raw()[..],
+        &ECMULT_GEN_CONTEXT.inspect_raw()[..]
+    );
+}
+
+#[test]
+fn test_parse_error_details() {
+    // Wrong sequence tag.
+    assert_eq!(
+        Signature::parse_der(&[0x31, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01]),
+        Err(Error::InvalidDer(DerError {
+            field: DerField::SequenceTag,
+            offset: 0,
+        }))
+    );
+    // Sequence length does not cover the rest of the input.
+    assert_eq!(
+        Signature::parse_der(&[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01]),
+        Err(Error::InvalidDer(DerError {
+            field: DerField::SequenceLength,
+            offset: 2,
+        }))
+    );
+    // Wrong tag for `s`.
+    assert_eq!(
+        Signature::parse_der(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x03, 0x01, 0x01]),
+        Err(Error::InvalidDer(DerError {
+            field: DerField::IntegerTag,
+            offset: 5,
+        }))
+    );
+    // Truncated `s`.
+    assert_eq!(
+        Signature::parse_der(&[0x30, 0x05, 0x02, 0x01, 0x01, 0x02, 0x01]),
+        Err(Error::InvalidDer(DerError {
+            field: DerField::IntegerLength,
+            offset: 7,
+        }))
+    );
+
+    let mut pubkey = [0u8; 33];
+    pubkey[0] = 0x04;
+    assert_eq!(
+        PublicKey::parse_compressed(&pubkey),
+        Err(Error::InvalidPublicKeyEncoding(PublicKeyError::InvalidTag(
+            0x04
+        )))
+    );
+
+    assert_eq!(
+        SecretKey::parse(&[0u8; 32]),
+        Err(Error::InvalidScalar(ScalarError::Zero))
+    );
+    assert_eq!(
+        SecretKey::parse(&[0xffu8; 32]),
+        Err(Error::InvalidScalar(ScalarError::Overflow))
+    );
+}
+
+#[test]
+fn test_sort_multisig_keys() {
+    fn parse_keys(keys: &[&str]) -> Vec<PublicKey> {
+        keys.iter()
+            .map(|key| {
+                let mut a = [0u8; 33];
+                from_hex(key, &mut a).unwrap();
+                PublicKey::parse_compressed(&a).unwrap()
+            })
+            .collect()
+    }
+
+    // BIP67 test vectors.
+    let mut keys = parse_keys(&[
+        "02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8",
+        "02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f",
+    ]);
+    sort_multisig_keys(&mut keys);
+    assert_eq!(
+        keys,
+        parse_keys(&[
+            "02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f",
+            "02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8",
+        ])
+    );
+
+    let mut keys = parse_keys(&[
+        "02632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed0",
+        "027735a29bae7780a9755fae7a1c4374c656ac6a69ea9f3697fda61bb99a4f3e77",
+        "02e2cc6bd5f45edd43bebe7cb9b675f0ce9ed3efe613b177588290ad188d11b404",
+    ]);
+    let sorted = keys.clone();
+    keys.reverse();
+    sort_multisig_keys(&mut keys);
+    assert_eq!(keys, sorted);
+
+    // BIP327 KeySort test vector.
+    let mut keys = parse_keys(&[
+        "02DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+        "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66",
+        "02DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EFF",
+        "02DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+    ]);
+    sort_musig_keys(&mut keys);
+    assert_eq!(
+        keys,
+        parse_keys(&[
+            "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66",
+            "02DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+            "02DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+            "02DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EFF",
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        ])
+    );
+}
+
+#[test]
+fn test_x_only_public_key() {
+    let mut generator = [0u8; 32];
+    from_hex(
+        "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        &mut generator,
+    )
+    .unwrap();
+
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    let one = SecretKey::parse(&one).unwrap();
+    let pubkey = PublicKey::from_secret_key(&one);
+    let (xonly, odd) = pubkey.x_only();
+    assert!(!odd);
+    assert_eq!(xonly.serialize(), generator);
+    assert_eq!(XOnlyPublicKey::parse(&generator).unwrap(), xonly);
+    assert_eq!(PublicKey::from(xonly), pubkey);
+
+    // -G has the same x coordinate and an odd y coordinate.
+    let mut minus_one = [0u8; 32];
+    from_hex(
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+        &mut minus_one,
+    )
+    .unwrap();
+    let mut neg = pubkey;
+    neg.tweak_mul_assign(&SecretKey::parse(&minus_one).unwrap())
+        .unwrap();
+    let (neg_xonly, neg_odd) = neg.x_only();
+    assert!(neg_odd);
+    assert_eq!(neg_xonly, xonly);
+    assert!(pubkey < neg);
+
+    assert!(XOnlyPublicKey::parse(&[0xff; 32]).is_err());
+
+    let other = XOnlyPublicKey::from(PublicKey::from_secret_key(
+        &SecretKey::parse(&[1u8; 32]).unwrap(),
+    ));
+    assert_eq!(xonly.cmp(&other), xonly.serialize().cmp(&other.serialize()));
+}
+
+#[test]
+fn test_ecdh_with_hasher() {
+    let seckey_a = SecretKey::parse(&[1u8; 32]).unwrap();
+    let seckey_b = SecretKey::parse(&[2u8; 32]).unwrap();
+    let pubkey_a = PublicKey::from_secret_key(&seckey_a);
+    let pubkey_b = PublicKey::from_secret_key(&seckey_b);
+
+    let x_ab = ecdh_with_hasher(&pubkey_b, &seckey_a, |x, _| *x).unwrap();
+    let x_ba = ecdh_with_hasher(&pubkey_a, &seckey_b, |x, _| *x).unwrap();
+    assert_eq!(x_ab, x_ba);
+
+    let mut shared = pubkey_b;
+    shared.tweak_mul_assign(&seckey_a).unwrap();
+    let full = shared.serialize();
+    let (x, y) = ecdh_with_hasher(&pubkey_b, &seckey_a, |x, y| (*x, *y)).unwrap();
+    assert_eq!(&full[1..33], &x[..]);
+    assert_eq!(&full[33..], &y[..]);
+}
@@ -1,116 +1,266 @@
-use lib::{verify, Message, PublicKey, Signature};
-use serde::Deserialize;
+//! Project Wycheproof test vectors.
+//!
+//! The vector files are read at runtime from `res/`, or from the directory
+//! in `WYCHEPROOF_DIR`, for example the `testvectors_v1` directory of a
+//! checkout of https://github.com/C2SP/wycheproof. They are not vendored,
+//! so the suites are ignored by default and fail if their file is missing:
+//!
+//! ```text
+//! WYCHEPROOF_DIR=/path/to/wycheproof/testvectors_v1 cargo test --test wycheproof -- --ignored
+//! ```
+
+use lib::{ecdh_with_hasher, verify, Message, PublicKey, PublicKeyFormat, SecretKey, Signature};
+use serde::{de::DeserializeOwned, Deserialize};
 use sha2::Digest;
-use std::collections::HashMap;
+use std::{env, fs, path::PathBuf};
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
-struct TestCollection {
+struct TestCollection<G> {
     algorithm: String,
-    generator_version: String,
     number_of_tests: usize,
-    header: Vec<String>,
-    notes: HashMap<String, String>,
-    schema: String,
-    test_groups: Vec<TestGroup>,
+    test_groups: Vec<G>,
+}
+
+#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+enum TestResult {
+    Valid,
+    Acceptable,
+    Invalid,
+}
+
+impl TestResult {
+    /// Check the outcome of a test case. "Acceptable" cases may go either
+    /// way.
+    fn check(self, tc_id: usize, comment: &str, passed: bool) {
+        match self {
+            TestResult::Valid => assert!(passed, "tcId {} should pass: {}", tc_id, comment),
+            TestResult::Invalid => assert!(!passed, "tcId {} should fail: {}", tc_id, comment),
+            TestResult::Acceptable => (),
+        }
+    }
+}
+
+/// Load a vector file.
+fn load<G: DeserializeOwned>(name: &str) -> TestCollection<G> {
+    let dir = env::var_os("WYCHEPROOF_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("res"));
+    let path = dir.join(name);
+
+    let data = fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "{}: {}; set WYCHEPROOF_DIR to the Wycheproof test vectors",
+            path.display(),
+            e
+        )
+    });
+    let collection: TestCollection<G> = serde_json::from_str(&data).unwrap();
+    println!(
+        "{}: {} tests of {}",
+        name, collection.number_of_tests, collection.algorithm
+    );
+    collection
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
-struct TestGroup {
-    key: TestKey,
-    key_der: String,
-    key_pem: String,
+struct EcdsaTestGroup {
+    key: EcdsaTestKey,
     sha: String,
     #[serde(rename = "type")]
     typ: String,
-    tests: Vec<TestUnit>,
+    tests: Vec<EcdsaTestUnit>,
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
-struct TestKey {
+struct EcdsaTestKey {
     curve: String,
     key_size: usize,
     #[serde(rename = "type")]
     typ: String,
     uncompressed: String,
-    wx: String,
-    wy: String,
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
-struct TestUnit {
+struct EcdsaTestUnit {
     tc_id: usize,
     comment: String,
     msg: String,
     sig: String,
     result: TestResult,
-    flags: Vec<String>,
 }
 
-#[derive(Deserialize, PartialEq, Eq, Debug)]
+/// Run an ECDSA verification suite, with `parse` decoding the signature
+/// encoding of the suite.
+fn run_ecdsa(name: &str, typ: &str, parse: fn(&[u8]) -> Option<Signature>) {
+    let collection = load::<EcdsaTestGroup>(name);
+
+    for group in collection.test_groups {
+        assert_eq!(group.typ, typ);
+        assert_eq!(group.sha, "SHA-256");
+        assert_eq!(group.key.typ, "EcPublicKey");
+        assert_eq!(group.key.curve, "secp256k1");
+        assert_eq!(group.key.key_size, 256);
+
+        let key_raw = hex::decode(&group.key.uncompressed).unwrap();
+        let key = PublicKey::parse_slice(&key_raw, None).unwrap();
+
+        for test in group.tests {
+            let msg = sha2::Sha256::digest(&hex::decode(&test.msg).unwrap());
+            let msg = Message::parse_slice(&msg).unwrap();
+            let sig = hex::decode(&test.sig).unwrap();
+
+            let passed = parse(&sig).is_some_and(|sig| verify(&msg, &sig, &key));
+            test.result.check(test.tc_id, &test.comment, passed);
+        }
+    }
+}
+
+#[test]
+#[ignore]
+fn test_ecdsa_der() {
+    // This library only parses strict DER, so "acceptable" legacy encodings
+    // are rejected.
+    run_ecdsa("ecdsa_secp256k1_sha256_test.json", "EcdsaVerify", |sig| {
+        Signature::parse_der(sig).ok()
+    });
+}
+
+#[test]
+#[ignore]
+fn test_ecdsa_p1363() {
+    run_ecdsa(
+        "ecdsa_secp256k1_sha256_p1363_test.json",
+        "EcdsaP1363Verify",
+        |sig| Signature::parse_standard_slice(sig).ok(),
+    );
+}
+
+#[test]
+#[ignore]
+fn test_ecdsa_bitcoin() {
+    // Bitcoin additionally requires low S values.
+    run_ecdsa(
+        "ecdsa_secp256k1_sha256_bitcoin_test.json",
+        "EcdsaBitcoinVerify",
+        |sig| {
+            Signature::parse_der(sig)
+                .ok()
+                .filter(|sig| !sig.s.is_high())
+        },
+    );
+}
+
+#[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-enum TestResult {
-    Valid,
-    Acceptable,
-    Invalid,
+struct EcdhTestGroup {
+    curve: String,
+    encoding: String,
+    #[serde(rename = "type")]
+    typ: String,
+    tests: Vec<EcdhTestUnit>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EcdhTestUnit {
+    tc_id: usize,
+    comment: String,
+    public: String,
+    private: String,
+    shared: String,
+    result: TestResult,
 }
 
-enum TestError {
-    MessageDecoding,
-    SignatureDecoding,
-    Verification,
+/// `SubjectPublicKeyInfo` prefixes for secp256k1 keys, uncompressed and
+/// compressed.
+const SPKI_PREFIX_FULL: &str = "3056301006072a8648ce3d020106052b8104000a034200";
+const SPKI_PREFIX_COMPRESSED: &str = "3036301006072a8648ce3d020106052b8104000a032200";
+
+/// Parse an encoded point. Hybrid encodings are not accepted here.
+fn parse_point(point: &[u8]) -> Option<PublicKey> {
+    match point.first() {
+        Some(0x04) => PublicKey::parse_slice(point, Some(PublicKeyFormat::Full)).ok(),
+        Some(0x02) | Some(0x03) => {
+            PublicKey::parse_slice(point, Some(PublicKeyFormat::Compressed)).ok()
+        }
+        _ => None,
+    }
 }
 
-fn test_unit(test: &TestUnit, key: &PublicKey) -> Result<(), TestError> {
-    println!("tcId: {}, comment: {}", test.tc_id, test.comment);
+/// Parse a DER `SubjectPublicKeyInfo`. Only the named secp256k1 curve is
+/// supported, and anything else is rejected.
+fn parse_spki(spki: &[u8]) -> Option<PublicKey> {
+    [SPKI_PREFIX_FULL, SPKI_PREFIX_COMPRESSED]
+        .iter()
+        .map(|prefix| hex::decode(prefix).unwrap())
+        .find_map(|prefix| spki.strip_prefix(&prefix[..]).and_then(parse_point))
+}
 
-    let msg_raw = hex::decode(&test.msg).unwrap();
-    let sig_raw = hex::decode(&test.sig).unwrap();
+/// Private keys are big-endian integers of any length.
+fn parse_private(private: &[u8]) -> Option<SecretKey> {
+    let start = private
+        .iter()
+        .position(|&b| b != 0)
+        .unwrap_or(private.len());
+    let private = &private[start..];
+    if private.len() > 32 {
+        return None;
+    }
 
-    let msg_hashed_raw = sha2::Sha256::digest(&msg_raw);
-    let msg = Message::parse_slice(&msg_hashed_raw).map_err(|_| TestError::MessageDecoding)?;
-    let sig = Signature::parse_der(&sig_raw).map_err(|_| TestError::SignatureDecoding)?;
+    let mut a = [0u8; 32];
+    a[32 - private.len()..].copy_from_slice(private);
+    SecretKey::parse(&a).ok()
+}
 
-    if verify(&msg, &sig, &key) {
-        Ok(())
-    } else {
-        Err(TestError::Verification)
+fn run_ecdh(name: &str, encoding: &str, parse: fn(&[u8]) -> Option<PublicKey>) {
+    let collection = load::<EcdhTestGroup>(name);
+
+    for group in collection.test_groups {
+        assert_eq!(group.typ, "EcdhTest");
+        assert_eq!(group.curve, "secp256k1");
+        assert_eq!(group.encoding, encoding);
+
+        for test in group.tests {
+            let public = hex::decode(&test.public).unwrap();
+            let private = parse_private(&hex::decode(&test.private).unwrap());
+            let expected = hex::decode(&test.shared).unwrap();
+
+            // Invalid public keys, such as points on the twist, must be
+            // rejected before any scalar multiplication.
+            let shared = parse(&public)
+                .zip(private)
+                .and_then(|(public, private)| ecdh_with_hasher(&public, &private, |x, _| *x).ok());
+            if test.result == TestResult::Valid {
+                assert_eq!(
+                    shared.map(|shared| shared.to_vec()),
+                    Some(expected),
+                    "tcId {}: {}",
+                    test.tc_id,
+                    test.comment
+                );
+            } else {
+                // Invalid cases have no shared secret to compare against, so
+                // any computed secret is a failure.
+                test.result
+                    .check(test.tc_id, &test.comment, shared.is_some());
+            }
+        }
     }
 }
 
-//#[test]
-//fn test_wycheproof() {
-//    let test_collection_str = include_str!("../res/ecdsa_secp256k1_sha256_test.json");
-//    let test_collection: TestCollection = serde_json::from_str(test_collection_str).unwrap();
-//
-//    for test_group in test_collection.test_groups {
-//        assert_eq!(test_group.key.typ, "EcPublicKey");
-//        assert_eq!(test_group.key.curve, "secp256k1");
-//        assert_eq!(test_group.key.key_size, 256);
-//
-//        let key_raw = hex::decode(test_group.key.uncompressed).unwrap();
-//        let key = libsecp256k1::PublicKey::parse_slice(&key_raw, None).unwrap();
-//
-//        for test in test_group.tests {
-//            let res = test_unit(&test, &key);
-//
-//            match res {
-//                Ok(()) => assert!(test.result == TestResult::Valid),
-//                Err(TestError::Verification) => assert_eq!(test.result, TestResult::Invalid),
-//                Err(TestError::MessageDecoding) => assert_eq!(test.result, TestResult::Invalid),
-//                // libsecp256k1 do not use any legacy formats, so "acceptable"
-//                // result in wycheproof is considered the same as invalid.
-//                Err(TestError::SignatureDecoding) => assert!(
-//                    test.result == TestResult::Acceptable || test.result == TestResult::Invalid
-//                ),
-//            }
-//        }
-//    }
-//}
+#[test]
+#[ignore]
+fn test_ecdh_spki() {
+    run_ecdh("ecdh_secp256k1_test.json", "asn", parse_spki);
+}
+
+#[test]
+#[ignore]
+fn test_ecdh_ecpoint() {
+    run_ecdh("ecdh_secp256k1_ecpoint_test.json", "ecpoint", parse_point);
+}